@@ -1,5 +1,13 @@
 use clap::{App, Arg};
 
+/// The old spellings of renamed flags, mapped to their current names. Each old spelling is
+/// also registered as a hidden [alias](Arg::alias) on its argument in [build], so configured
+/// users keep working while the warning in [main](crate) nags them to move over.
+///
+/// This file is included by the build script, so the deprecation warning itself lives in
+/// `main.rs` where the error printing macros are available.
+pub const DEPRECATED_ALIASES: &[(&str, &str)] = &[("literal", "raw")];
+
 pub fn build() -> App<'static, 'static> {
     App::new("lsd")
         .version(crate_version!())
@@ -32,6 +40,89 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("When to use terminal colours"),
         )
+        .arg(
+            Arg::with_name("color-source")
+                .long("color-source")
+                .possible_value("ls-colors")
+                .possible_value("theme")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Whether the LS_COLORS environment variable is honored on top of the theme"),
+        )
+        .arg(
+            Arg::with_name("color-override")
+                .long("color-override")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("fragment")
+                .number_of_values(1)
+                .help("Apply an LS_COLORS-style fragment (e.g. 'di=34;1' or '*.log=90') on top of the environment's colors. More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("theme")
+                .long("theme")
+                .possible_value("auto")
+                .possible_value("default")
+                .possible_value("light")
+                .possible_value("high-contrast")
+                .possible_value("deuteranopia")
+                .default_value("auto")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which color palette to use, with auto picking one based on the terminal background"),
+        )
+        .arg(
+            Arg::with_name("theme-preview")
+                .long("theme-preview")
+                .multiple(true)
+                .help("Render a synthetic listing exercising every themable category instead of listing files"),
+        )
+        .arg(
+            Arg::with_name("min-contrast")
+                .long("min-contrast")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("ratio")
+                .number_of_values(1)
+                .help("Adjust theme colors which fall below the given contrast ratio against the background"),
+        )
+        .arg(
+            Arg::with_name("follow-links-at-depth")
+                .long("follow-links-at-depth")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .help("Expand symlinked directories in the tree layout only down to the given depth"),
+        )
+        .arg(
+            Arg::with_name("git")
+                .long("git")
+                .multiple(true)
+                .help("Show the git status of each entry in an additional block"),
+        )
+        .arg(
+            Arg::with_name("git-ignore")
+                .long("git-ignore")
+                .multiple(true)
+                .help("Do not display entries ignored by git or by an .ignore file in the listed directory"),
+        )
+        .arg(
+            Arg::with_name("hyperlink")
+                .long("hyperlink")
+                .possible_value("always")
+                .possible_value("auto")
+                .possible_value("never")
+                .default_value("never")
+                .multiple(true)
+                .number_of_values(1)
+                .help("When to wrap the file names in clickable OSC 8 terminal hyperlinks"),
+        )
+        .arg(
+            Arg::with_name("header")
+                .long("header")
+                .multiple(true)
+                .help("Print a caption row above the blocks in the one-line layouts"),
+        )
         .arg(
             Arg::with_name("icon")
                 .long("icon")
@@ -48,11 +139,24 @@ pub fn build() -> App<'static, 'static> {
                 .long("icon-theme")
                 .possible_value("fancy")
                 .possible_value("unicode")
+                .possible_value("emoji")
                 .default_value("fancy")
                 .multiple(true)
                 .number_of_values(1)
                 .help("Whether to use fancy or unicode icons"),
         )
+        .arg(
+            Arg::with_name("extension-stats")
+                .long("extension-stats")
+                .multiple(true)
+                .help("Annotate directories with the extension counts of their immediate children"),
+        )
+        .arg(
+            Arg::with_name("fast-network-fs")
+                .long("fast-network-fs")
+                .multiple(true)
+                .help("Skip metadata probes which are slow on network mounts, like symlink target stats"),
+        )
         .arg(
             Arg::with_name("indicators")
                 .short("F")
@@ -60,6 +164,33 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Append indicator (one of */=>@|) at the end of the file names"),
         )
+        .arg(
+            Arg::with_name("index")
+                .long("index")
+                .possible_value("build")
+                .takes_value(true)
+                .value_name("action")
+                .number_of_values(1)
+                .help("Maintain the persistent directory-size index consulted by --total-size"),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .multiple(true)
+                .help("Print the listing as JSON, nesting children arrays in the tree layout"),
+        )
+        .arg(
+            Arg::with_name("keep-arg-order")
+                .long("keep-arg-order")
+                .multiple(true)
+                .help("List path arguments in the order given instead of sorting them"),
+        )
+        .arg(
+            Arg::with_name("keep-duplicates")
+                .long("keep-duplicates")
+                .multiple(true)
+                .help("List path arguments resolving to the same entry more than once"),
+        )
         .arg(
             Arg::with_name("long")
                 .short("l")
@@ -72,6 +203,12 @@ pub fn build() -> App<'static, 'static> {
                 .long("ignore-config")
                 .help("Ignore the configuration file"),
         )
+        .arg(
+            Arg::with_name("mount-info")
+                .long("mount-info")
+                .multiple(true)
+                .help("Print the filesystem type and key mount options of each listed path"),
+        )
         .arg(
             Arg::with_name("oneline")
                 .short("1")
@@ -79,10 +216,58 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display one entry per line"),
         )
+        .arg(
+            Arg::with_name("output-format")
+                .long("output-format")
+                .possible_value("default")
+                .possible_value("dot")
+                .multiple(true)
+                .number_of_values(1)
+                .help("The output format, with dot emitting the listing as a Graphviz digraph"),
+        )
+        .arg(
+            Arg::with_name("padding")
+                .long("padding")
+                .possible_value("space")
+                .possible_value("figure-space")
+                .possible_value("zeros")
+                .multiple(true)
+                .number_of_values(1)
+                .help("The character padding the numeric columns, with figure-space surviving proportional fonts"),
+        )
+        .arg(
+            Arg::with_name("parents")
+                .long("parents")
+                .multiple(true)
+                .help("List each path itself plus all of its ancestor directories, like namei"),
+        )
+        .arg(
+            Arg::with_name("print0-fields")
+                .long("print0-fields")
+                .multiple(true)
+                .number_of_values(1)
+                .require_delimiter(true)
+                .possible_values(&["name", "path", "size", "mtime", "type"])
+                .help("Print records of the given fields, separated by the unit separator and terminated by NUL"),
+        )
+        .arg(
+            Arg::with_name("raw")
+                .long("raw")
+                .alias("literal")
+                .multiple(true)
+                .help("Print entries without icons, colors, grid layout or padding, keeping sorting and filtering"),
+        )
+        .arg(
+            Arg::with_name("watch")
+                .long("watch")
+                .multiple(true)
+                .help("Redraw the listing periodically, re-reading the configuration file when it changes"),
+        )
         .arg(
             Arg::with_name("recursive")
                 .short("R")
                 .long("recursive")
+                .alias("recurse")
                 .multiple(true)
                 .conflicts_with("tree")
                 .help("Recurse into directories"),
@@ -100,6 +285,14 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("recursive")
                 .help("Recurse into directories and present the result as a tree"),
         )
+        .arg(
+            Arg::with_name("tree-indent")
+                .long("tree-indent")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("num")
+                .help("Use plain indentation of the specified width instead of branch characters in the tree layout"),
+        )
         .arg(
             Arg::with_name("depth")
                 .long("depth")
@@ -108,6 +301,12 @@ pub fn build() -> App<'static, 'static> {
                 .value_name("num")
                 .help("Stop recursing into directories after reaching specified depth"),
         )
+        .arg(
+            Arg::with_name("disk-usage")
+                .long("disk-usage")
+                .multiple(true)
+                .help("Print a footer with the used, free and total space of each listed path's filesystem"),
+        )
         .arg(
             Arg::with_name("directory-only")
                 .short("d")
@@ -119,6 +318,12 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("tree")
                 .help("Display directories themselves, and not their contents"),
         )
+        .arg(
+            Arg::with_name("resolve")
+                .long("resolve")
+                .multiple(true)
+                .help("Print the fully resolved path of each argument, making symlink chains visible"),
+        )
         .arg(
             Arg::with_name("size")
                 .long("size")
@@ -130,6 +335,90 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("How to display size"),
         )
+        .arg(
+            Arg::with_name("size-source")
+                .long("size-source")
+                .possible_value("apparent")
+                .possible_value("blocks")
+                .default_value("apparent")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Whether sizes report the apparent file size or the allocated disk blocks"),
+        )
+        .arg(
+            Arg::with_name("size-unit")
+                .long("size-unit")
+                .possible_value("default")
+                .possible_value("binary")
+                .possible_value("decimal")
+                .default_value("default")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Whether size units use binary (KiB, 1024) or decimal (KB, 1000) prefixes"),
+        )
+        .arg(
+            Arg::with_name("size-align")
+                .long("size-align")
+                .multiple(true)
+                .help("Align size values on the decimal point and keep units in a fixed column"),
+        )
+        .arg(
+            Arg::with_name("case-check")
+                .long("case-check")
+                .multiple(true)
+                .help("On case-insensitive filesystems, warn about names differing only by case within a directory"),
+        )
+        .arg(
+            Arg::with_name("check-access")
+                .long("check-access")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("name")
+                .number_of_values(1)
+                .help("Display the effective access of the given user or group to each entry in an additional block"),
+        )
+        .arg(
+            Arg::with_name("extended")
+                .long("extended")
+                .multiple(true)
+                .help("List the extended attribute names and values beneath each entry in long view"),
+        )
+        .arg(
+            Arg::with_name("expect-mode")
+                .long("expect-mode")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("files:dirs")
+                .help("Highlight permission bits deviating from the given expected octal modes (e.g. '644:755')"),
+        )
+        .arg(
+            Arg::with_name("foreign-owner")
+                .long("foreign-owner")
+                .multiple(true)
+                .help("Highlight entries whose owner differs from the one of the containing directory"),
+        )
+        .arg(
+            Arg::with_name("permission")
+                .long("permission")
+                .possible_value("rwx")
+                .possible_value("octal")
+                .possible_value("spaced")
+                .possible_value("attributes")
+                .possible_value("disable")
+                .default_value(if cfg!(windows) { "attributes" } else { "rwx" })
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display permissions"),
+        )
+        .arg(
+            Arg::with_name("top")
+                .long("top")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .help("List only the given number of largest files, gathered across the whole recursive walk"),
+        )
         .arg(
             Arg::with_name("total-size")
                 .long("total-size")
@@ -143,7 +432,18 @@ pub fn build() -> App<'static, 'static> {
                 .default_value("date")
                 .multiple(true)
                 .number_of_values(1)
-                .help("How to display date [possible values: date, relative, +date-time-format]"),
+                .help("How to display date [possible values: date, relative, combined, +date-time-format]"),
+        )
+        .arg(
+            Arg::with_name("time-precision")
+                .long("time-precision")
+                .possible_value("s")
+                .possible_value("ms")
+                .possible_value("ns")
+                .default_value("s")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Precision of the seconds in displayed timestamps"),
         )
         .arg(
             Arg::with_name("timesort")
@@ -193,7 +493,7 @@ pub fn build() -> App<'static, 'static> {
             Arg::with_name("sort")
                 .long("sort")
                 .multiple(true)
-                .possible_values(&["size", "time", "version", "extension"])
+                .possible_values(&["size", "time", "version", "extension", "type", "inode"])
                 .takes_value(true)
                 .value_name("WORD")
                 .overrides_with("timesort")
@@ -220,6 +520,23 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Sort the directories then the files"),
         )
+        .arg(
+            Arg::with_name("group-symlinks")
+                .long("group-symlinks")
+                .possible_value("none")
+                .possible_value("first")
+                .possible_value("last")
+                .default_value("none")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Group the symlinks before or after the other entries"),
+        )
+        .arg(
+            Arg::with_name("bit-hints")
+                .long("bit-hints")
+                .multiple(true)
+                .help("Append a dimmed explanation to directories carrying the setgid or sticky bit"),
+        )
         .arg(
             Arg::with_name("blocks")
                 .long("blocks")
@@ -231,17 +548,112 @@ pub fn build() -> App<'static, 'static> {
                     "user",
                     "group",
                     "size",
+                    "size_value",
                     "date",
+                    "btime",
+                    "accessed-age",
                     "name",
                     "inode",
+                    "links",
+                    "context",
+                    "access",
+                    "git",
+                    "type-icon",
                 ])
                 .help("Specify the blocks that will be displayed and in what order"),
         )
+        .arg(
+            Arg::with_name("max-width")
+                .long("max-width")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("block=width")
+                .number_of_values(1)
+                .help("Truncate the given block when it exceeds the width (e.g. 'group=8'). More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("collapse-owner")
+                .long("collapse-owner")
+                .multiple(true)
+                .help("Omit the user and group blocks when they are the same for every listed entry, printing them once in a header instead"),
+        )
+        .arg(
+            Arg::with_name("copy")
+                .long("copy")
+                .multiple(true)
+                .help("Besides printing, copy the listed paths to the clipboard through an OSC 52 escape sequence"),
+        )
+        .arg(
+            Arg::with_name("count")
+                .long("count")
+                .multiple(true)
+                .help("Print per-directory entry counts instead of the entries themselves"),
+        )
+        .arg(
+            Arg::with_name("collapse")
+                .long("collapse")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("pattern")
+                .number_of_values(1)
+                .help("Collapse runs of entries matching the glob into one summary row with a count and combined size. More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("crowded-dirs")
+                .long("crowded-dirs")
+                .multiple(true)
+                .help("Color the name of directories holding 1000 entries or more with the dir.crowded theme color"),
+        )
+        .arg(
+            Arg::with_name("date-granularity")
+                .long("date-granularity")
+                .possible_values(&["rough", "precise"])
+                .default_value("rough")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How detailed the relative date style is"),
+        )
+        .arg(
+            Arg::with_name("date-field")
+                .long("date-field")
+                .possible_values(&["mod", "access", "change", "birth"])
+                .default_value("mod")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which timestamp the date column and the time sort use"),
+        )
+        .arg(
+            Arg::with_name("context")
+                .short("Z")
+                .long("context")
+                .multiple(true)
+                .help("Display the SELinux or SMACK security context of each entry"),
+        )
         .arg(
             Arg::with_name("classic")
             .long("classic")
             .help("Enable classic mode (no colors or icons)"),
         )
+        .arg(
+            Arg::with_name("deterministic")
+                .long("deterministic")
+                .multiple(true)
+                .help("Force one-line layout, no colors or icons, UTC timestamps and a fixed date format for byte-identical output"),
+        )
+        .arg(
+            Arg::with_name("link-chain")
+                .long("link-chain")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .help("Show the resolution chain of nested symlinks up to the given number of hops"),
+        )
+        .arg(
+            Arg::with_name("no-external")
+                .long("no-external")
+                .multiple(true)
+                .help("Spawn no processes and read nothing outside the listed paths and config (disables git status and the size index)"),
+        )
         .arg(
             Arg::with_name("no-symlink")
                 .long("no-symlink")
@@ -258,6 +670,14 @@ pub fn build() -> App<'static, 'static> {
                 .default_value("")
                 .help("Do not display files/directories with names matching the glob pattern(s). More than one can be specified by repeating the argument"),
         )
+        .arg(
+            Arg::with_name("ignore-file")
+                .long("ignore-file")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("path")
+                .help("Do not display entries matching the glob patterns listed in the given file, one pattern per line"),
+        )
         .arg(
             Arg::with_name("inode")
                 .short("i")
@@ -265,6 +685,63 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display the index number of each file"),
         )
+        .arg(
+            Arg::with_name("sids")
+                .long("sids")
+                .multiple(true)
+                .help("Display raw SIDs instead of resolved account names in the user and group blocks (Windows only)"),
+        )
+        .arg(
+            Arg::with_name("no-windows-attributes")
+                .long("no-windows-attributes")
+                .multiple(true)
+                .help("Do not treat entries with the Windows Hidden or System attribute as hidden"),
+        )
+        .arg(
+            Arg::with_name("peers")
+                .long("peers")
+                .multiple(true)
+                .help("Display peer information for sockets and FIFOs (Linux only)"),
+        )
+        .arg(
+            Arg::with_name("umask-check")
+                .long("umask-check")
+                .multiple(true)
+                .help("Highlight permission bits deviating from what the current umask would produce"),
+        )
+        .arg(
+            Arg::with_name("uid-map")
+                .long("uid-map")
+                .multiple(true)
+                .takes_value(true)
+                .value_name("path")
+                .number_of_values(1)
+                .help("Map user and group ids through a uid_map-style file (e.g. /proc/<pid>/uid_map) before display"),
+        )
+        .arg(
+            Arg::with_name("units")
+                .long("units")
+                .multiple(true)
+                .help("Annotate entries held open by a systemd unit with the unit name (Linux only)"),
+        )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .multiple(true)
+                .help("Print a footer with the file, directory, byte totals and the largest file across the listing"),
+        )
+        .arg(
+            Arg::with_name("stdin")
+                .long("stdin")
+                .multiple(true)
+                .help("Read the paths to list from stdin instead of the FILE arguments"),
+        )
+        .arg(
+            Arg::with_name("strict-reset")
+                .long("strict-reset")
+                .multiple(true)
+                .help("Reset the terminal style after every cell and keep trailing padding unstyled, avoiding background-color bleed in some terminals and tmux"),
+        )
         .arg(
             Arg::with_name("dereference")
                 .short("L")
@@ -277,7 +754,7 @@ pub fn build() -> App<'static, 'static> {
 fn validate_date_argument(arg: String) -> Result<(), String> {
     if arg.starts_with('+') {
         validate_time_format(&arg).map_err(|err| err.to_string())
-    } else if &arg == "date" || &arg == "relative" {
+    } else if &arg == "date" || &arg == "relative" || &arg == "combined" {
         Result::Ok(())
     } else {
         Result::Err("possible values: date, relative, +date-time-format".to_owned())
@@ -1,7 +1,11 @@
+use crate::config_file::Config;
+use crate::print_error;
+
 use ansi_term::{ANSIString, Colour, Style};
 use lscolors::{Indicator, LsColors};
 use std::collections::HashMap;
 use std::path::Path;
+use yaml_rust::{Yaml, YamlLoader};
 
 #[allow(dead_code)]
 #[derive(Hash, Debug, Eq, PartialEq, Clone)]
@@ -16,6 +20,8 @@ pub enum Elem {
     Dir {
         uid: bool,
     },
+    /// A directory holding more entries than the crowded threshold.
+    CrowdedDir,
     Pipe,
     BlockDevice,
     CharDevice,
@@ -28,6 +34,7 @@ pub enum Elem {
     Exec,
     ExecSticky,
     NoAccess,
+    Octal,
 
     /// Last Time Modified
     DayOld,
@@ -43,11 +50,32 @@ pub enum Elem {
     FileLarge,
     FileMedium,
     FileSmall,
+    SizeUnit,
 
     /// INode
     INode {
         valid: bool,
     },
+
+    /// Hard link count
+    Links {
+        valid: bool,
+    },
+
+    /// SELinux or SMACK security label
+    Context,
+
+    /// A read-only or immutable entry.
+    Locked,
+
+    /// A permission bit deviating from what the umask would produce.
+    PermissionAnomaly,
+
+    /// Git status
+    GitStaged,
+    GitModified,
+    GitUntracked,
+    GitIgnored,
 }
 
 impl Elem {
@@ -66,17 +94,244 @@ pub enum Theme {
     NoLscolors,
 }
 
+/// A registry of the built-in color palettes. Each variant maps to one colour map, so new
+/// palettes only have to provide a map and a name here without touching the rendering code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Palette {
+    Default,
+    Light,
+    HighContrast,
+    Deuteranopia,
+}
+
+impl Palette {
+    fn colour_map(self) -> HashMap<Elem, Colour> {
+        match self {
+            Self::Default => Colors::get_default_colour_map(),
+            Self::Light => Colors::get_light_colour_map(),
+            Self::HighContrast => Colors::get_high_contrast_colour_map(),
+            Self::Deuteranopia => Colors::get_deuteranopia_colour_map(),
+        }
+    }
+
+    /// The palette with the given name, as used by the `extends` key of the theme file.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::Default),
+            "light" => Some(Self::Light),
+            "high-contrast" => Some(Self::HighContrast),
+            "deuteranopia" => Some(Self::Deuteranopia),
+            _ => None,
+        }
+    }
+}
+
+/// A user theme from `theme.yaml` next to the configuration file: the palette it extends and
+/// the colors it overrides. Only the overridden keys need to be declared, so a small
+/// customization does not have to copy a whole palette.
+pub struct ThemeFile {
+    pub extends: Option<Palette>,
+    overrides: HashMap<Elem, Colour>,
+}
+
+impl ThemeFile {
+    /// Load the theme file, if one exists.
+    pub fn load() -> Option<Self> {
+        let (long, short) = Config::config_file_paths()?;
+        let directory = long.parent()?;
+
+        let content = std::fs::read_to_string(directory.join("theme.yaml"))
+            .or_else(|_| std::fs::read_to_string(directory.join("theme.yml")))
+            .ok()?;
+        let _ = short;
+
+        match YamlLoader::load_from_str(&content) {
+            Ok(documents) if !documents.is_empty() => Some(Self::from_yaml(&documents[0])),
+            Ok(_) => None,
+            Err(error) => {
+                print_error!("Error parsing theme file: {}\n", error);
+                None
+            }
+        }
+    }
+
+    fn from_yaml(yaml: &Yaml) -> Self {
+        let mut theme = Self {
+            extends: None,
+            overrides: HashMap::new(),
+        };
+
+        if let Yaml::Hash(hash) = yaml {
+            for (key, value) in hash {
+                let key = match key {
+                    Yaml::String(key) => key,
+                    _ => continue,
+                };
+
+                if key == "extends" {
+                    match value {
+                        Yaml::String(name) => match Palette::from_name(name) {
+                            Some(palette) => theme.extends = Some(palette),
+                            None => {
+                                print_error!("Not a valid theme to extend: {}\n", name);
+                            }
+                        },
+                        _ => {
+                            print_error!("The extends key of the theme must be a string\n");
+                        }
+                    }
+                    continue;
+                }
+
+                theme.collect(value, key);
+            }
+        }
+
+        theme
+    }
+
+    /// Collect the overrides below the given key, recursing into nested sections like
+    /// `permission`, so the file can mirror the grouping of the palette.
+    fn collect(&mut self, yaml: &Yaml, prefix: &str) {
+        match yaml {
+            Yaml::Hash(hash) => {
+                for (key, value) in hash {
+                    if let Yaml::String(key) = key {
+                        self.collect(value, &format!("{}.{}", prefix, key));
+                    }
+                }
+            }
+            Yaml::Integer(number) if (0..=255).contains(number) => {
+                self.insert(prefix, Colour::Fixed(*number as u8));
+            }
+            Yaml::String(value) => match colour_from_name(value).or_else(|| colour_from_hex(value))
+            {
+                Some(colour) => self.insert(prefix, colour),
+                None => {
+                    print_error!("Not a valid theme color for {}: {}\n", prefix, value);
+                }
+            },
+            _ => {
+                print_error!(
+                    "The theme color for {} must be a number between 0 and 255, a color name \
+                     or a #rrggbb code\n",
+                    prefix
+                );
+            }
+        }
+    }
+
+    fn insert(&mut self, key: &str, colour: Colour) {
+        match elem_from_key(key) {
+            Some(elem) => {
+                self.overrides.insert(elem, colour);
+            }
+            None => {
+                print_error!("Not a valid theme key: {}\n", key);
+            }
+        }
+    }
+}
+
+/// The colour for one of the classic palette names, with a `bright-` prefix selecting the
+/// light half.
+fn colour_from_name(name: &str) -> Option<Colour> {
+    let names = [
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+    ];
+
+    let (base, offset) = match name.strip_prefix("bright-") {
+        Some(base) => (base, 8),
+        None => (name, 0),
+    };
+
+    names
+        .iter()
+        .position(|candidate| *candidate == base)
+        .map(|position| Colour::Fixed(position as u8 + offset))
+}
+
+/// The truecolor for a `#rrggbb` code.
+fn colour_from_hex(code: &str) -> Option<Colour> {
+    let digits = code.strip_prefix('#')?;
+    if digits.len() != 6 {
+        return None;
+    }
+
+    let channel = |index| u8::from_str_radix(&digits[index..index + 2], 16).ok();
+    Some(Colour::RGB(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// The [Elem] for each key of the theme file.
+fn elem_from_key(key: &str) -> Option<Elem> {
+    match key {
+        "file" => Some(Elem::File {
+            exec: false,
+            uid: false,
+        }),
+        "file.exec" => Some(Elem::File {
+            exec: true,
+            uid: false,
+        }),
+        "file.uid" => Some(Elem::File {
+            exec: false,
+            uid: true,
+        }),
+        "file.uid-exec" => Some(Elem::File {
+            exec: true,
+            uid: true,
+        }),
+        "dir" => Some(Elem::Dir { uid: false }),
+        "dir.uid" => Some(Elem::Dir { uid: true }),
+        "dir.crowded" => Some(Elem::CrowdedDir),
+        "symlink" => Some(Elem::SymLink),
+        "broken-symlink" => Some(Elem::BrokenSymLink),
+        "pipe" => Some(Elem::Pipe),
+        "block-device" => Some(Elem::BlockDevice),
+        "char-device" => Some(Elem::CharDevice),
+        "socket" => Some(Elem::Socket),
+        "special" => Some(Elem::Special),
+        "permission.read" => Some(Elem::Read),
+        "permission.write" => Some(Elem::Write),
+        "permission.exec" => Some(Elem::Exec),
+        "permission.exec-sticky" => Some(Elem::ExecSticky),
+        "permission.no-access" => Some(Elem::NoAccess),
+        "permission.octal" => Some(Elem::Octal),
+        "permission.anomaly" => Some(Elem::PermissionAnomaly),
+        "date.hour-old" => Some(Elem::HourOld),
+        "date.day-old" => Some(Elem::DayOld),
+        "date.older" => Some(Elem::Older),
+        "user" => Some(Elem::User),
+        "group" => Some(Elem::Group),
+        "size.none" => Some(Elem::NonFile),
+        "size.small" => Some(Elem::FileSmall),
+        "size.medium" => Some(Elem::FileMedium),
+        "size.large" => Some(Elem::FileLarge),
+        "size.unit" => Some(Elem::SizeUnit),
+        "inode.valid" => Some(Elem::INode { valid: true }),
+        "inode.invalid" => Some(Elem::INode { valid: false }),
+        "links.valid" => Some(Elem::Links { valid: true }),
+        "links.invalid" => Some(Elem::Links { valid: false }),
+        "context" => Some(Elem::Context),
+        "locked" => Some(Elem::Locked),
+        "git.staged" => Some(Elem::GitStaged),
+        "git.modified" => Some(Elem::GitModified),
+        "git.untracked" => Some(Elem::GitUntracked),
+        "git.ignored" => Some(Elem::GitIgnored),
+        _ => None,
+    }
+}
+
 pub struct Colors {
     colors: Option<HashMap<Elem, Colour>>,
     lscolors: Option<LsColors>,
 }
 
 impl Colors {
-    pub fn new(theme: Theme) -> Self {
+    pub fn new(theme: Theme, palette: Palette) -> Self {
         let colors = match theme {
             Theme::NoColor => None,
-            Theme::Default => Some(Self::get_light_theme_colour_map()),
-            Theme::NoLscolors => Some(Self::get_light_theme_colour_map()),
+            Theme::Default | Theme::NoLscolors => Some(palette.colour_map()),
         };
         let lscolors = match theme {
             Theme::NoColor => None,
@@ -87,6 +342,41 @@ impl Colors {
         Self { colors, lscolors }
     }
 
+    /// Apply `LS_COLORS`-style fragments on top of the color mapping taken from the
+    /// environment. Later fragments win over earlier ones and over the environment.
+    /// Apply the overridden colors of the user theme on top of the palette.
+    pub fn with_theme_file(mut self, theme: &ThemeFile) -> Self {
+        if let Some(colors) = &mut self.colors {
+            for (elem, colour) in &theme.overrides {
+                colors.insert(elem.clone(), *colour);
+            }
+        }
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: &[String]) -> Self {
+        if self.lscolors.is_some() && !overrides.is_empty() {
+            let mut input = std::env::var("LS_COLORS").unwrap_or_default();
+            for fragment in overrides {
+                input.push(':');
+                input.push_str(fragment);
+            }
+            self.lscolors = Some(LsColors::from_string(&input));
+        }
+        self
+    }
+
+    /// Post-process the colour map so every colour reaches at least the given contrast ratio
+    /// against the background, nudging the lightness of failing colours until they do.
+    pub fn with_min_contrast(mut self, ratio: f32, background: (u8, u8, u8)) -> Self {
+        if let Some(colors) = &mut self.colors {
+            for colour in colors.values_mut() {
+                *colour = ensure_contrast(*colour, ratio, background);
+            }
+        }
+        self
+    }
+
     pub fn colorize<'a>(&self, input: String, elem: &Elem) -> ColoredString<'a> {
         self.style(elem).paint(input)
     }
@@ -177,7 +467,7 @@ impl Colors {
     // You can find the table for each color, code, and display at:
     //
     //https://jonasjacek.github.io/colors/
-    fn get_light_theme_colour_map() -> HashMap<Elem, Colour> {
+    fn get_default_colour_map() -> HashMap<Elem, Colour> {
         let mut m = HashMap::new();
         // User / Group
         m.insert(Elem::User, Colour::Fixed(230)); // Cornsilk1
@@ -189,6 +479,13 @@ impl Colors {
         m.insert(Elem::Exec, Colour::Red);
         m.insert(Elem::ExecSticky, Colour::Purple);
         m.insert(Elem::NoAccess, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Octal, Colour::Fixed(152)); // LightCyan3
+
+        // Git status
+        m.insert(Elem::GitStaged, Colour::Fixed(71)); // DarkSeaGreen4
+        m.insert(Elem::GitModified, Colour::Fixed(178)); // Gold3
+        m.insert(Elem::GitUntracked, Colour::Fixed(160)); // Red3
+        m.insert(Elem::GitIgnored, Colour::Fixed(245)); // Grey
 
         // File Types
         m.insert(
@@ -221,6 +518,7 @@ impl Colors {
         ); // Green3
         m.insert(Elem::Dir { uid: true }, Colour::Fixed(33)); // DodgerBlue1
         m.insert(Elem::Dir { uid: false }, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::CrowdedDir, Colour::Fixed(208)); // DarkOrange
         m.insert(Elem::Pipe, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::SymLink, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::BrokenSymLink, Colour::Fixed(124)); // Red3
@@ -239,11 +537,375 @@ impl Colors {
         m.insert(Elem::FileSmall, Colour::Fixed(229)); // Wheat1
         m.insert(Elem::FileMedium, Colour::Fixed(216)); // LightSalmon1
         m.insert(Elem::FileLarge, Colour::Fixed(172)); // Orange3
+        m.insert(Elem::SizeUnit, Colour::Fixed(246)); // Grey
+        m.insert(Elem::Locked, Colour::Fixed(172)); // Orange3
+        m.insert(Elem::PermissionAnomaly, Colour::Fixed(196)); // Red1
 
         // INode
         m.insert(Elem::INode { valid: true }, Colour::Fixed(13)); // Pink
         m.insert(Elem::INode { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(13)); // Pink
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Context, Colour::Fixed(109)); // LightSkyBlue3
 
         m
     }
+
+    /// The colour map of the light palette, which keeps the hues of the default palette but
+    /// picks darker shades that stay readable on a white background.
+    fn get_light_colour_map() -> HashMap<Elem, Colour> {
+        let mut m = HashMap::new();
+        // User / Group
+        m.insert(Elem::User, Colour::Fixed(94)); // Orange4
+        m.insert(Elem::Group, Colour::Fixed(58)); // Yellow4
+
+        // Permissions
+        m.insert(Elem::Read, Colour::Fixed(22)); // DarkGreen
+        m.insert(Elem::Write, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::Exec, Colour::Red);
+        m.insert(Elem::ExecSticky, Colour::Purple);
+        m.insert(Elem::NoAccess, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Octal, Colour::Fixed(30)); // Turquoise4
+
+        // Git status
+        m.insert(Elem::GitStaged, Colour::Fixed(28)); // Green4
+        m.insert(Elem::GitModified, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::GitUntracked, Colour::Fixed(124)); // Red3
+        m.insert(Elem::GitIgnored, Colour::Fixed(243)); // Grey
+
+        // File Types
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: false,
+            },
+            Colour::Fixed(100),
+        ); // Yellow4
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: true,
+            },
+            Colour::Fixed(100),
+        ); // Yellow4
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: false,
+            },
+            Colour::Fixed(28),
+        ); // Green4
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: true,
+            },
+            Colour::Fixed(28),
+        ); // Green4
+        m.insert(Elem::Dir { uid: true }, Colour::Fixed(25)); // DeepSkyBlue4
+        m.insert(Elem::Dir { uid: false }, Colour::Fixed(25)); // DeepSkyBlue4
+        m.insert(Elem::CrowdedDir, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::Pipe, Colour::Fixed(30)); // Turquoise4
+        m.insert(Elem::SymLink, Colour::Fixed(30)); // Turquoise4
+        m.insert(Elem::BrokenSymLink, Colour::Fixed(124)); // Red3
+        m.insert(Elem::BlockDevice, Colour::Fixed(30)); // Turquoise4
+        m.insert(Elem::CharDevice, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::Socket, Colour::Fixed(30)); // Turquoise4
+        m.insert(Elem::Special, Colour::Fixed(30)); // Turquoise4
+
+        // Last Time Modified
+        m.insert(Elem::HourOld, Colour::Fixed(28)); // Green4
+        m.insert(Elem::DayOld, Colour::Fixed(29)); // SpringGreen4
+        m.insert(Elem::Older, Colour::Fixed(23)); // DeepSkyBlue4
+
+        // File Size
+        m.insert(Elem::NonFile, Colour::Fixed(245)); // Grey
+        m.insert(Elem::FileSmall, Colour::Fixed(100)); // Yellow4
+        m.insert(Elem::FileMedium, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::FileLarge, Colour::Fixed(94)); // Orange4
+        m.insert(Elem::SizeUnit, Colour::Fixed(243)); // Grey
+        m.insert(Elem::Locked, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::PermissionAnomaly, Colour::Fixed(124)); // Red3
+
+        // INode
+        m.insert(Elem::INode { valid: true }, Colour::Fixed(5)); // Purple
+        m.insert(Elem::INode { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(5)); // Purple
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Context, Colour::Fixed(30)); // Turquoise4
+
+        m
+    }
+
+    /// The colour map of the high-contrast palette, which sticks to the bright half of the
+    /// classic sixteen color palette so every element stands out on common backgrounds.
+    fn get_high_contrast_colour_map() -> HashMap<Elem, Colour> {
+        let mut m = HashMap::new();
+        // User / Group
+        m.insert(Elem::User, Colour::Fixed(15)); // White
+        m.insert(Elem::Group, Colour::Fixed(14)); // Aqua
+
+        // Permissions
+        m.insert(Elem::Read, Colour::Fixed(10)); // Lime
+        m.insert(Elem::Write, Colour::Fixed(11)); // Yellow
+        m.insert(Elem::Exec, Colour::Fixed(9)); // Red
+        m.insert(Elem::ExecSticky, Colour::Fixed(13)); // Fuchsia
+        m.insert(Elem::NoAccess, Colour::Fixed(7)); // Silver
+        m.insert(Elem::Octal, Colour::Fixed(14)); // Aqua
+
+        // Git status
+        m.insert(Elem::GitStaged, Colour::Fixed(10)); // Lime
+        m.insert(Elem::GitModified, Colour::Fixed(11)); // Yellow
+        m.insert(Elem::GitUntracked, Colour::Fixed(9)); // Red
+        m.insert(Elem::GitIgnored, Colour::Fixed(7)); // Silver
+
+        // File Types
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: false,
+            },
+            Colour::Fixed(15),
+        ); // White
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: true,
+            },
+            Colour::Fixed(15),
+        ); // White
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: false,
+            },
+            Colour::Fixed(10),
+        ); // Lime
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: true,
+            },
+            Colour::Fixed(10),
+        ); // Lime
+        m.insert(Elem::Dir { uid: true }, Colour::Fixed(12)); // Blue
+        m.insert(Elem::Dir { uid: false }, Colour::Fixed(12)); // Blue
+        m.insert(Elem::CrowdedDir, Colour::Fixed(9)); // Red
+        m.insert(Elem::Pipe, Colour::Fixed(14)); // Aqua
+        m.insert(Elem::SymLink, Colour::Fixed(14)); // Aqua
+        m.insert(Elem::BrokenSymLink, Colour::Fixed(9)); // Red
+        m.insert(Elem::BlockDevice, Colour::Fixed(14)); // Aqua
+        m.insert(Elem::CharDevice, Colour::Fixed(11)); // Yellow
+        m.insert(Elem::Socket, Colour::Fixed(14)); // Aqua
+        m.insert(Elem::Special, Colour::Fixed(14)); // Aqua
+
+        // Last Time Modified
+        m.insert(Elem::HourOld, Colour::Fixed(10)); // Lime
+        m.insert(Elem::DayOld, Colour::Fixed(14)); // Aqua
+        m.insert(Elem::Older, Colour::Fixed(12)); // Blue
+
+        // File Size
+        m.insert(Elem::NonFile, Colour::Fixed(7)); // Silver
+        m.insert(Elem::FileSmall, Colour::Fixed(15)); // White
+        m.insert(Elem::FileMedium, Colour::Fixed(11)); // Yellow
+        m.insert(Elem::FileLarge, Colour::Fixed(9)); // Red
+        m.insert(Elem::SizeUnit, Colour::Fixed(7)); // Silver
+        m.insert(Elem::Locked, Colour::Fixed(9)); // Red
+        m.insert(Elem::PermissionAnomaly, Colour::Fixed(9)); // Red
+        // INode
+        m.insert(Elem::INode { valid: true }, Colour::Fixed(13)); // Fuchsia
+        m.insert(Elem::INode { valid: false }, Colour::Fixed(7)); // Silver
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(13)); // Fuchsia
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(7)); // Silver
+        m.insert(Elem::Context, Colour::Fixed(14)); // Aqua
+
+        m
+    }
+
+    /// The colour map of the deuteranopia-friendly palette, which avoids distinctions on the
+    /// red-green axis and leans on blues, oranges and yellows instead.
+    fn get_deuteranopia_colour_map() -> HashMap<Elem, Colour> {
+        let mut m = HashMap::new();
+        // User / Group
+        m.insert(Elem::User, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::Group, Colour::Fixed(180)); // Tan
+
+        // Permissions
+        m.insert(Elem::Read, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::Write, Colour::Fixed(208)); // DarkOrange
+        m.insert(Elem::Exec, Colour::Fixed(220)); // Gold1
+        m.insert(Elem::ExecSticky, Colour::Fixed(129)); // Purple
+        m.insert(Elem::NoAccess, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Octal, Colour::Fixed(74)); // SkyBlue3
+
+        // Git status
+        m.insert(Elem::GitStaged, Colour::Fixed(26)); // Blue
+        m.insert(Elem::GitModified, Colour::Fixed(208)); // DarkOrange
+        m.insert(Elem::GitUntracked, Colour::Fixed(94)); // Orange4
+        m.insert(Elem::GitIgnored, Colour::Fixed(245)); // Grey
+
+        // File Types
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: false,
+            },
+            Colour::Fixed(187),
+        ); // LightYellow3
+        m.insert(
+            Elem::File {
+                exec: false,
+                uid: true,
+            },
+            Colour::Fixed(187),
+        ); // LightYellow3
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: false,
+            },
+            Colour::Fixed(208),
+        ); // DarkOrange
+        m.insert(
+            Elem::File {
+                exec: true,
+                uid: true,
+            },
+            Colour::Fixed(208),
+        ); // DarkOrange
+        m.insert(Elem::Dir { uid: true }, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::Dir { uid: false }, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::CrowdedDir, Colour::Fixed(208)); // DarkOrange
+        m.insert(Elem::Pipe, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::SymLink, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::BrokenSymLink, Colour::Fixed(166)); // DarkOrange3
+        m.insert(Elem::BlockDevice, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::CharDevice, Colour::Fixed(180)); // Tan
+        m.insert(Elem::Socket, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::Special, Colour::Fixed(74)); // SkyBlue3
+
+        // Last Time Modified
+        m.insert(Elem::HourOld, Colour::Fixed(39)); // DeepSkyBlue1
+        m.insert(Elem::DayOld, Colour::Fixed(74)); // SkyBlue3
+        m.insert(Elem::Older, Colour::Fixed(67)); // SteelBlue
+
+        // File Size
+        m.insert(Elem::NonFile, Colour::Fixed(245)); // Grey
+        m.insert(Elem::FileSmall, Colour::Fixed(187)); // LightYellow3
+        m.insert(Elem::FileMedium, Colour::Fixed(215)); // SandyBrown
+        m.insert(Elem::FileLarge, Colour::Fixed(208)); // DarkOrange
+        m.insert(Elem::SizeUnit, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Locked, Colour::Fixed(208)); // DarkOrange
+        m.insert(Elem::PermissionAnomaly, Colour::Fixed(160)); // Red3
+
+        // INode
+        m.insert(Elem::INode { valid: true }, Colour::Fixed(135)); // MediumPurple2
+        m.insert(Elem::INode { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(135)); // MediumPurple2
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Context, Colour::Fixed(74)); // SkyBlue3
+
+        m
+    }
+}
+
+/// The red, green and blue channels of a colour, using the xterm defaults for the fixed ones.
+fn colour_channels(colour: Colour) -> (u8, u8, u8) {
+    // The xterm defaults of the classic sixteen color palette.
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    // Values 16 to 231 form a colour cube with these channel levels, the values above hold a
+    // twenty-four step grayscale ramp.
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match colour {
+        Colour::Black => BASIC[0],
+        Colour::Red => BASIC[1],
+        Colour::Green => BASIC[2],
+        Colour::Yellow => BASIC[3],
+        Colour::Blue => BASIC[4],
+        Colour::Purple => BASIC[5],
+        Colour::Cyan => BASIC[6],
+        Colour::White => BASIC[7],
+        Colour::Fixed(value @ 0..=15) => BASIC[usize::from(value)],
+        Colour::Fixed(value @ 16..=231) => {
+            let index = usize::from(value - 16);
+            (
+                CUBE_LEVELS[index / 36],
+                CUBE_LEVELS[(index / 6) % 6],
+                CUBE_LEVELS[index % 6],
+            )
+        }
+        Colour::Fixed(value) => {
+            let gray = 8 + 10 * (value - 232);
+            (gray, gray, gray)
+        }
+        Colour::RGB(red, green, blue) => (red, green, blue),
+    }
+}
+
+/// The relative luminance of a colour as defined by the WCAG.
+fn relative_luminance((red, green, blue): (u8, u8, u8)) -> f32 {
+    fn channel(value: u8) -> f32 {
+        let value = f32::from(value) / 255.0;
+        if value <= 0.039_28 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    0.2126 * channel(red) + 0.7152 * channel(green) + 0.0722 * channel(blue)
+}
+
+/// The WCAG contrast ratio between two colours, ranging from 1 to 21.
+fn contrast_ratio(first: (u8, u8, u8), second: (u8, u8, u8)) -> f32 {
+    let first = relative_luminance(first);
+    let second = relative_luminance(second);
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// Move a colour towards black on light backgrounds and towards white on dark ones, until the
+/// requested contrast ratio is reached or the colour cannot get any further away.
+fn ensure_contrast(colour: Colour, ratio: f32, background: (u8, u8, u8)) -> Colour {
+    let original = colour_channels(colour);
+    if contrast_ratio(original, background) >= ratio {
+        return colour;
+    }
+
+    let target = if relative_luminance(background) > 0.5 {
+        0.0
+    } else {
+        255.0
+    };
+
+    let mut adjusted = original;
+    for step in 1..=10 {
+        let amount = step as f32 / 10.0;
+        let blend =
+            |value: u8| (f32::from(value) + (target - f32::from(value)) * amount).round() as u8;
+
+        adjusted = (blend(original.0), blend(original.1), blend(original.2));
+        if contrast_ratio(adjusted, background) >= ratio {
+            break;
+        }
+    }
+
+    Colour::RGB(adjusted.0, adjusted.1, adjusted.2)
 }
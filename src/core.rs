@@ -1,10 +1,13 @@
-use crate::color::{self, Colors};
+use crate::color::{self, Colors, Elem};
 use crate::display;
-use crate::flags::{ColorOption, Display, Flags, IconOption, IconTheme, Layout, SortOrder};
+use crate::flags::{
+    Block, ColorOption, Display, Flags, IconOption, IconTheme, Layout, SortOrder, ThemeFlag,
+};
 use crate::icon::{self, Icons};
-use crate::meta::Meta;
+use crate::meta::{FileType, Meta, Name, Size};
 use crate::{print_error, print_output, sort};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 #[cfg(not(target_os = "windows"))]
 use std::io;
@@ -24,6 +27,18 @@ pub struct Core {
 
 impl Core {
     pub fn new(flags: Flags) -> Self {
+        let mut flags = flags;
+
+        // Snapshot tests want the same bytes on every machine: render like a pipe, pin the
+        // timestamp format and render dates in UTC regardless of the host zone.
+        if flags.deterministic.0 {
+            std::env::set_var("TZ", "UTC");
+            flags.date = crate::flags::DateFlag::Formatted("%Y-%m-%d %H:%M:%S".into());
+            flags.icons.when = IconOption::Never;
+            flags.color.when = ColorOption::Never;
+            flags.hyperlink = crate::flags::HyperlinkOption::Never;
+        }
+
         // Check through libc if stdout is a tty. Unix specific so not on windows.
         // Determine color output availability (and initialize color output (for Windows 10))
         #[cfg(not(target_os = "windows"))]
@@ -38,17 +53,55 @@ impl Core {
         #[cfg(target_os = "windows")]
         let console_color_ok = ansi_term::enable_ansi_support().is_ok();
 
+        // Without terminal detection the layout and widths cannot vary with the terminal.
+        let tty_available = tty_available && !flags.deterministic.0;
+
         let mut inner_flags = flags.clone();
 
         let color_theme = match (tty_available && console_color_ok, flags.color.when) {
+            _ if flags.raw.0 => color::Theme::NoColor,
             (_, ColorOption::Never) | (false, ColorOption::Auto) => color::Theme::NoColor,
+            // With --color-source=theme the palette alone drives the colors, so an exotic
+            // LS_COLORS from the environment can not leak into the output.
+            _ if flags.color_source == crate::flags::ColorSource::Theme => {
+                color::Theme::NoLscolors
+            }
             _ => color::Theme::Default,
         };
 
+        let theme_file = color::ThemeFile::load();
+
+        let palette = match flags.theme {
+            ThemeFlag::Default => color::Palette::Default,
+            ThemeFlag::Light => color::Palette::Light,
+            ThemeFlag::HighContrast => color::Palette::HighContrast,
+            ThemeFlag::Deuteranopia => color::Palette::Deuteranopia,
+            ThemeFlag::Auto => {
+                // An explicit flag beats the theme file, but with auto the file's `extends`
+                // names the palette its overrides were written against.
+                if let Some(color::ThemeFile {
+                    extends: Some(palette),
+                    ..
+                }) = &theme_file
+                {
+                    *palette
+                } else if has_light_background() {
+                    color::Palette::Light
+                } else {
+                    color::Palette::Default
+                }
+            }
+        };
+
         let icon_theme = match (tty_available, flags.icons.when, flags.icons.theme) {
+            _ if flags.raw.0 => icon::Theme::NoIcon,
             (_, IconOption::Never, _) | (false, IconOption::Auto, _) => icon::Theme::NoIcon,
+            // With auto, skip the glyphs on terminals known to lack the fonts for them;
+            // `--icon always` still forces them on.
+            (_, IconOption::Auto, _) if terminal_lacks_icons() => icon::Theme::NoIcon,
             (_, _, IconTheme::Fancy) => icon::Theme::Fancy,
             (_, _, IconTheme::Unicode) => icon::Theme::Unicode,
+            (_, _, IconTheme::Emoji) => icon::Theme::Emoji,
         };
 
         if !tty_available {
@@ -59,25 +112,283 @@ impl Core {
             inner_flags.layout = Layout::OneLine;
         };
 
+        let mut flags = flags;
+
+        // The hyperlinks only help on a terminal which interprets them; pipes get the plain
+        // names. Resolving auto here keeps the display code to a simple equality check.
+        if flags.hyperlink == crate::flags::HyperlinkOption::Auto {
+            flags.hyperlink = if tty_available {
+                crate::flags::HyperlinkOption::Always
+            } else {
+                crate::flags::HyperlinkOption::Never
+            };
+        }
+
         let sorters = sort::assemble_sorters(&flags);
 
+        let mut colors =
+            Colors::new(color_theme, palette).with_overrides(&flags.color_overrides.0);
+        if let Some(theme_file) = &theme_file {
+            colors = colors.with_theme_file(theme_file);
+        }
+        if let Some(ratio) = flags.contrast.ratio {
+            let background = flags.contrast.background.unwrap_or(match palette {
+                color::Palette::Light => (255, 255, 255),
+                _ => (0, 0, 0),
+            });
+            colors = colors.with_min_contrast(ratio, background);
+        }
+
+        let icons = Icons::new(icon_theme).with_overrides(flags.icons.overrides.clone());
+
         Self {
             flags,
             //display: Display::new(inner_flags),
-            colors: Colors::new(color_theme),
-            icons: Icons::new(icon_theme),
+            colors,
+            icons,
             sorters,
         }
     }
 
     pub fn run(self, paths: Vec<PathBuf>) {
+        print_output!("{}", self.render(paths));
+    }
+
+    /// The rendered listing as one string, for callers which post-process the output, like
+    /// the watch mode annotating the rows which changed between refreshes.
+    pub fn render(mut self, mut paths: Vec<PathBuf>) -> String {
+        // A theme preview needs no paths at all, so it short-circuits the whole fetch.
+        if self.flags.theme_preview.0 {
+            return self.render_theme_preview();
+        }
+
+        // Inside a restrictive sandbox every probe beyond the listed paths is off limits:
+        // no git subprocess, no size index, no filesystem table reads and no procfs scans.
+        if self.flags.no_external.0 {
+            self.flags.git_ignore = crate::flags::GitIgnore(false);
+            self.flags.fast_network_fs = crate::flags::FastNetworkFs(false);
+            self.flags.mount_info = crate::flags::MountInfo(false);
+            self.flags.peers = crate::flags::Peers(false);
+            self.flags.units = crate::flags::Units(false);
+            crate::index::disable();
+        }
+
+        crate::meta::set_fast_network_fs(self.flags.fast_network_fs.0);
+        crate::meta::set_git_status(
+            self.flags.blocks.0.contains(&Block::GitStatus) && !self.flags.no_external.0,
+        );
+        crate::meta::set_xattrs(
+            self.flags.blocks.0.contains(&Block::Permission)
+                || self.flags.blocks.0.contains(&Block::Context)
+                || self.flags.extended.0,
+        );
+        crate::meta::set_date_field(self.flags.date_field);
+        crate::meta::set_size_from_blocks(self.flags.size_source == crate::flags::SizeSource::Blocks);
+
+        // With --parents every argument is replaced by its ancestor chain, listed from the
+        // root down like namei(1), so the entries themselves are shown in argument order.
+        if self.flags.parents.0 {
+            paths = paths
+                .iter()
+                .flat_map(|path| parent_chain(path))
+                .collect();
+            self.flags.display = Display::DirectoryItself;
+            self.flags.keep_arg_order = crate::flags::KeepArgOrder(true);
+        }
+
+        // Arguments like `.` and `./` or two spellings of the same directory land on the
+        // same entry, so listing them twice is rarely wanted and opt-in.
+        if !self.flags.keep_duplicates.0 {
+            let mut seen = HashSet::new();
+            paths.retain(|path| seen.insert(dedup_key(path)));
+        }
+
+        if self.flags.blocks.0.contains(&Block::AccessedAge) && !self.flags.no_external.0 {
+            if let Some(path) = paths.first() {
+                warn_coarse_atime(path);
+            }
+        }
+
+        // Hunting the biggest files only makes sense across the whole tree, so --top walks
+        // it without also asking for -R.
+        if self.flags.top.0.is_some() {
+            self.flags.recursion.enabled = true;
+        }
+
         let mut meta_list = self.fetch(paths);
 
-        self.sort(&mut meta_list);
+        if self.flags.case_check.0 && case_insensitive_filesystem() {
+            warn_case_clashes(&meta_list);
+        }
+
+        if self.flags.total_size.0 {
+            crate::index::flush();
+        }
+
+        if let Some(limit) = self.flags.top.0 {
+            meta_list = Self::top_entries(meta_list, limit);
+        }
+
+        self.sort_roots(&mut meta_list);
+
+        if !self.flags.collapse_globs.0.is_empty() {
+            self.collapse_runs(&mut meta_list);
+        }
+
         self.display(&meta_list)
     }
 
+    /// Collapse runs of two or more consecutive entries whose names match the collapse
+    /// globs into one summary row carrying the run's combined size, so a thousand rotated
+    /// logs take one line instead of drowning the listing.
+    fn collapse_runs(&self, metas: &mut Vec<Meta>) {
+        for meta in metas.iter_mut() {
+            if let Some(content) = &mut meta.content {
+                self.collapse_runs(content);
+            }
+        }
+
+        let mut collapsed: Vec<Meta> = Vec::with_capacity(metas.len());
+        let mut run: Vec<Meta> = Vec::new();
+
+        for meta in metas.drain(..) {
+            let matches = meta
+                .path
+                .file_name()
+                .map(|name| self.flags.collapse_globs.0.is_match(name))
+                .unwrap_or(false);
+
+            if matches {
+                run.push(meta);
+            } else {
+                Self::flush_run(&mut run, &mut collapsed);
+                collapsed.push(meta);
+            }
+        }
+        Self::flush_run(&mut run, &mut collapsed);
+
+        *metas = collapsed;
+    }
+
+    /// Turn a finished run into its summary row, or keep its entries as they are when the
+    /// run is too short to be worth collapsing.
+    fn flush_run(run: &mut Vec<Meta>, collapsed: &mut Vec<Meta>) {
+        if run.len() < 2 {
+            collapsed.append(run);
+            return;
+        }
+
+        let total: u64 = run.iter().map(|meta| meta.size.get_bytes()).sum();
+        let count = run.len();
+        let last_name = run[count - 1].name.name.clone();
+
+        let mut summary = run.remove(0);
+        let summary_name = format!(
+            "{} \u{2026} {} ({} entries)",
+            summary.name.name, last_name, count
+        );
+        // The rendered name comes from the path, so the summary text goes there and the
+        // [Name] is rebuilt from it.
+        summary.path = summary.path.with_file_name(summary_name);
+        summary.name = Name::new(&summary.path, summary.file_type);
+        summary.size = Size::new(total);
+        summary.content = None;
+
+        collapsed.push(summary);
+        run.clear();
+    }
+
+    /// Keep only the given number of largest files of the listing, flattened into one list
+    /// from largest to smallest. A bounded min-heap holds the current candidates, so the
+    /// rest of the tree can be dropped as soon as it is outgrown.
+    fn top_entries(metas: Vec<Meta>, limit: usize) -> Vec<Meta> {
+        use std::cmp::{Ordering, Reverse};
+        use std::collections::BinaryHeap;
+
+        struct Candidate {
+            bytes: u64,
+            /// The position in the walk, breaking size ties in favor of the earlier entry.
+            order: Reverse<usize>,
+            meta: Meta,
+        }
+
+        impl Ord for Candidate {
+            fn cmp(&self, other: &Self) -> Ordering {
+                (self.bytes, self.order).cmp(&(other.bytes, other.order))
+            }
+        }
+
+        impl PartialOrd for Candidate {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl PartialEq for Candidate {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for Candidate {}
+
+        fn collect(
+            metas: Vec<Meta>,
+            heap: &mut BinaryHeap<Reverse<Candidate>>,
+            limit: usize,
+            order: &mut usize,
+        ) {
+            for mut meta in metas {
+                let content = meta.content.take();
+
+                if !matches!(meta.file_type, FileType::Directory { .. }) {
+                    heap.push(Reverse(Candidate {
+                        bytes: meta.size.get_bytes(),
+                        order: Reverse(*order),
+                        meta,
+                    }));
+                    *order += 1;
+
+                    if heap.len() > limit {
+                        heap.pop();
+                    }
+                }
+
+                if let Some(content) = content {
+                    collect(content, heap, limit, order);
+                }
+            }
+        }
+
+        let mut heap = BinaryHeap::with_capacity(limit + 1);
+        let mut order = 0;
+        collect(metas, &mut heap, limit, &mut order);
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(candidate)| candidate.meta)
+            .collect()
+    }
+
+    fn sort_roots(&self, metas: &mut Vec<Meta>) {
+        // With --keep-arg-order the arguments stay in the order they were given, while their
+        // contents are still sorted normally.
+        if self.flags.keep_arg_order.0 {
+            for meta in metas {
+                if let Some(ref mut content) = meta.content {
+                    self.sort(content);
+                }
+            }
+        } else {
+            self.sort(metas);
+        }
+    }
+
     fn fetch(&self, paths: Vec<PathBuf>) -> Vec<Meta> {
+        if self.flags.stdin.0 && self.flags.layout == Layout::Tree {
+            return self.fetch_from_path_list(paths);
+        }
+
         let mut meta_list = Vec::with_capacity(paths.len());
         let depth = match self.flags.layout {
             Layout::Tree { .. } => self.flags.recursion.depth,
@@ -121,8 +432,81 @@ impl Core {
         meta_list
     }
 
+    /// Reconstruct a tree of [Meta] from a flat list of paths, like the ones read on stdin.
+    ///
+    /// The paths are linked to their closest listed ancestor, so the output of commands like
+    /// `git ls-files` or `fd` can be rendered as a tree without walking the file system again.
+    fn fetch_from_path_list(&self, paths: Vec<PathBuf>) -> Vec<Meta> {
+        let mut children: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        let mut roots: Vec<PathBuf> = Vec::new();
+        let mut known: HashSet<PathBuf> = HashSet::new();
+
+        for path in paths {
+            let mut current = path;
+
+            while known.insert(current.clone()) {
+                match current.parent() {
+                    Some(parent) if parent != Path::new("") => {
+                        children
+                            .entry(parent.to_path_buf())
+                            .or_insert_with(Vec::new)
+                            .push(current.clone());
+                        current = parent.to_path_buf();
+                    }
+                    _ => {
+                        roots.push(current);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut meta_list = Vec::with_capacity(roots.len());
+        for root in &roots {
+            if let Some(meta) = self.meta_from_path_list(root, &children) {
+                meta_list.push(meta);
+            }
+        }
+
+        if self.flags.total_size.0 {
+            for meta in &mut meta_list.iter_mut() {
+                meta.calculate_total_size();
+            }
+        }
+
+        meta_list
+    }
+
+    /// Build the [Meta] for a listed path, attaching the [Meta] of the listed paths directly
+    /// below it as its content.
+    fn meta_from_path_list(
+        &self,
+        path: &Path,
+        children: &HashMap<PathBuf, Vec<PathBuf>>,
+    ) -> Option<Meta> {
+        let mut meta = match Meta::from_path(path, self.flags.dereference.0) {
+            Ok(meta) => meta,
+            Err(err) => {
+                print_error!("lsd: {}: {}\n", path.display(), err);
+                return None;
+            }
+        };
+
+        if let Some(child_paths) = children.get(path) {
+            let mut content = Vec::with_capacity(child_paths.len());
+            for child in child_paths {
+                if let Some(child_meta) = self.meta_from_path_list(child, children) {
+                    content.push(child_meta);
+                }
+            }
+            meta.content = Some(content);
+        }
+
+        Some(meta)
+    }
+
     fn sort(&self, metas: &mut Vec<Meta>) {
-        metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
+        sort::sort_metas(metas, &self.flags, &self.sorters);
 
         for meta in metas {
             if let Some(ref mut content) = meta.content {
@@ -131,13 +515,564 @@ impl Core {
         }
     }
 
-    fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
-            display::tree(&metas, &self.flags, &self.colors, &self.icons)
+    fn display(&self, metas: &[Meta]) -> String {
+        let mut flags = &self.flags;
+
+        // Everything is gathered into one buffer and flushed in a single write; many small
+        // writes are measurably slower on slow terminals and remote sessions.
+        let mut output = String::new();
+
+        // When the owner and group are uniform across the listing they carry no information
+        // per entry, so drop their blocks and print them once up front instead.
+        let mut collapsed_flags;
+        if self.flags.collapse_owner.0 {
+            if let Some((user, group)) = Self::uniform_owner(metas) {
+                collapsed_flags = self.flags.clone();
+                collapsed_flags
+                    .blocks
+                    .0
+                    .retain(|block| *block != Block::User && *block != Block::Group);
+
+                if collapsed_flags.blocks.0.len() < self.flags.blocks.0.len() {
+                    flags = &collapsed_flags;
+                    output += &format!("Owner: {} {}\n", user, group);
+                }
+            }
+        }
+
+        if flags.resolve.0 {
+            for meta in metas {
+                if let Ok(canonical) = meta.path.canonicalize() {
+                    if canonical != meta.path {
+                        output += &format!("{} -> {}\n", meta.path.display(), canonical.display());
+                        continue;
+                    }
+                }
+                output += &format!("{}\n", meta.path.display());
+            }
+        }
+
+        if flags.mount_info.0 {
+            for meta in metas {
+                if let Some((fstype, options)) = mount_of(&meta.path) {
+                    // Only the options which commonly explain surprising failures.
+                    let interesting: Vec<&str> = options
+                        .split(',')
+                        .filter(|option| {
+                            matches!(*option, "ro" | "rw" | "noexec" | "nosuid" | "nodev")
+                        })
+                        .collect();
+
+                    output += &format!(
+                        "{}: {} ({})\n",
+                        meta.path.display(),
+                        fstype,
+                        interesting.join(",")
+                    );
+                }
+            }
+        }
+
+        output += &if flags.output_format == crate::flags::OutputFormat::Dot {
+            crate::dot::render(metas)
+        } else if flags.count.0 {
+            self.render_counts(metas)
+        } else if flags.json.0 {
+            crate::json::render(&metas, flags)
+        } else if !flags.print0_fields.0.is_empty() {
+            display::print0(&metas, flags)
+        } else if flags.layout == Layout::Tree {
+            display::tree(&metas, flags, &self.colors, &self.icons)
         } else {
-            display::grid(&metas, &self.flags, &self.colors, &self.icons)
+            display::grid(&metas, flags, &self.colors, &self.icons)
         };
 
-        print_output!("{}", output);
+        if self.flags.summary.0 {
+            output += &self.render_summary(metas);
+        }
+
+        if self.flags.disk_usage.0 {
+            for meta in metas {
+                if let Some(footer) = self.render_disk_usage(meta) {
+                    output += &footer;
+                }
+            }
+        }
+
+        // The OSC 52 sequence rides along with the regular output; terminals which do not
+        // support it simply ignore the escape, so there is nothing to feature-detect.
+        if self.flags.copy_paths.0 {
+            let mut paths = Vec::new();
+            Self::collect_paths(metas, &mut paths);
+            output += &format!(
+                "\u{1b}]52;c;{}\u{7}",
+                base64_encode(paths.join("\n").as_bytes())
+            );
+        }
+
+        output
+    }
+
+    /// Gather the paths of all listed entries, including the ones pulled in by recursion, in
+    /// display order.
+    fn collect_paths(metas: &[Meta], paths: &mut Vec<String>) {
+        for meta in metas {
+            if let Some(name) = meta.path.file_name() {
+                if name == "." || name == ".." {
+                    continue;
+                }
+            }
+
+            paths.push(meta.path.display().to_string());
+
+            if let Some(content) = &meta.content {
+                Self::collect_paths(content, paths);
+            }
+        }
+    }
+
+    /// Build a synthetic listing with one swatch per themable category, so theme authors can
+    /// check every key of their theme file in one shot. The keys match the ones accepted by
+    /// `theme.yaml`.
+    fn render_theme_preview(&self) -> String {
+        let samples: &[(&str, Elem)] = &[
+            (
+                "file",
+                Elem::File {
+                    exec: false,
+                    uid: false,
+                },
+            ),
+            (
+                "file.exec",
+                Elem::File {
+                    exec: true,
+                    uid: false,
+                },
+            ),
+            (
+                "file.uid",
+                Elem::File {
+                    exec: false,
+                    uid: true,
+                },
+            ),
+            (
+                "file.uid-exec",
+                Elem::File {
+                    exec: true,
+                    uid: true,
+                },
+            ),
+            ("dir", Elem::Dir { uid: false }),
+            ("dir.uid", Elem::Dir { uid: true }),
+            ("dir.crowded", Elem::CrowdedDir),
+            ("symlink", Elem::SymLink),
+            ("broken-symlink", Elem::BrokenSymLink),
+            ("pipe", Elem::Pipe),
+            ("block-device", Elem::BlockDevice),
+            ("char-device", Elem::CharDevice),
+            ("socket", Elem::Socket),
+            ("special", Elem::Special),
+            ("permission.read", Elem::Read),
+            ("permission.write", Elem::Write),
+            ("permission.exec", Elem::Exec),
+            ("permission.exec-sticky", Elem::ExecSticky),
+            ("permission.no-access", Elem::NoAccess),
+            ("permission.octal", Elem::Octal),
+            ("permission.anomaly", Elem::PermissionAnomaly),
+            ("date.hour-old", Elem::HourOld),
+            ("date.day-old", Elem::DayOld),
+            ("date.older", Elem::Older),
+            ("user", Elem::User),
+            ("group", Elem::Group),
+            ("size.none", Elem::NonFile),
+            ("size.small", Elem::FileSmall),
+            ("size.medium", Elem::FileMedium),
+            ("size.large", Elem::FileLarge),
+            ("size.unit", Elem::SizeUnit),
+            ("inode.valid", Elem::INode { valid: true }),
+            ("inode.invalid", Elem::INode { valid: false }),
+            ("context", Elem::Context),
+            ("locked", Elem::Locked),
+            ("git.staged", Elem::GitStaged),
+            ("git.modified", Elem::GitModified),
+            ("git.untracked", Elem::GitUntracked),
+            ("git.ignored", Elem::GitIgnored),
+        ];
+
+        let mut output = String::new();
+        for (key, elem) in samples {
+            output += &format!(
+                "{} {}\n",
+                self.colors.colorize(String::from("\u{2588}\u{2588}\u{2588} sample"), elem),
+                key
+            );
+        }
+
+        output
+    }
+
+    /// Build the filesystem usage footer for one listed path, if the space information is
+    /// available on this platform.
+    fn render_disk_usage(&self, meta: &Meta) -> Option<String> {
+        let (total, free) = filesystem_space(&meta.path)?;
+        let used = total.saturating_sub(free);
+
+        let render = |bytes: u64| {
+            let size = Size::new(bytes);
+            format!(
+                "{} {}",
+                size.value_string(&self.flags),
+                size.unit_string(&self.flags)
+            )
+        };
+
+        Some(format!(
+            "{}: {} used, {} free, {} total\n",
+            meta.path.display(),
+            render(used),
+            render(free),
+            render(total)
+        ))
+    }
+
+    /// Build one line of counts per listed directory instead of the entries themselves, a
+    /// faster `ls | wc -l` which still honors the filters and the recursion depth. Entries
+    /// pulled in by recursion get their own line each.
+    fn render_counts(&self, metas: &[Meta]) -> String {
+        let mut output = String::new();
+
+        for meta in metas {
+            let content = match &meta.content {
+                Some(content) => content,
+                None => continue,
+            };
+
+            let (mut files, mut dirs, mut hidden) = (0, 0, 0);
+            let mut bytes = 0;
+            for entry in content {
+                // The `.` and `..` entries of -a say nothing about the directory itself.
+                if entry.name.name == "." || entry.name.name == ".." {
+                    continue;
+                }
+
+                if entry.name.name.starts_with('.') {
+                    hidden += 1;
+                }
+
+                if matches!(entry.file_type, FileType::Directory { .. }) {
+                    dirs += 1;
+                } else {
+                    files += 1;
+                    bytes += entry.size.get_bytes();
+                }
+            }
+
+            output += &format!(
+                "{}: {} files, {} dirs, {} hidden",
+                meta.path.display(),
+                files,
+                dirs,
+                hidden
+            );
+
+            if self.flags.total_size.0 {
+                // With --total-size the directory's own size already accumulates everything
+                // below it, which is more useful here than the direct children alone.
+                let total = Size::new(meta.size.get_bytes().max(bytes));
+                output += &format!(
+                    ", {} {} total",
+                    total.value_string(&self.flags),
+                    total.unit_string(&self.flags)
+                );
+            }
+            output += "\n";
+
+            output += &self.render_counts(content);
+        }
+
+        output
+    }
+
+    /// Build the grand-total footer across every entry of the listing, including the ones
+    /// gathered by recursion, so `-R` and `--tree` report what was actually visited.
+    fn render_summary(&self, metas: &[Meta]) -> String {
+        fn collect<'a>(
+            metas: &'a [Meta],
+            files: &mut usize,
+            dirs: &mut usize,
+            bytes: &mut u64,
+            largest: &mut Option<(&'a Meta, u64)>,
+        ) {
+            for meta in metas {
+                if matches!(meta.file_type, FileType::Directory { .. }) {
+                    *dirs += 1;
+                } else {
+                    *files += 1;
+                    let size = meta.size.get_bytes();
+                    *bytes += size;
+
+                    if largest.map(|(_, largest)| largest < size).unwrap_or(true) {
+                        *largest = Some((meta, size));
+                    }
+                }
+
+                if let Some(content) = &meta.content {
+                    collect(content, files, dirs, bytes, largest);
+                }
+            }
+        }
+
+        let (mut files, mut dirs, mut bytes) = (0, 0, 0);
+        let mut largest = None;
+        collect(metas, &mut files, &mut dirs, &mut bytes, &mut largest);
+
+        let total = Size::new(bytes);
+        let mut summary = format!(
+            "{} files, {} dirs, {} {} total",
+            files,
+            dirs,
+            total.value_string(&self.flags),
+            total.unit_string(&self.flags)
+        );
+
+        if let Some((meta, _)) = largest {
+            summary += &format!(
+                ", largest: {} ({} {})",
+                meta.path.display(),
+                meta.size.value_string(&self.flags),
+                meta.size.unit_string(&self.flags)
+            );
+        }
+
+        summary + "\n"
+    }
+
+    /// Get the user and group names shared by every entry of the listing, if they are the same
+    /// everywhere.
+    fn uniform_owner(metas: &[Meta]) -> Option<(String, String)> {
+        fn collect<'a>(metas: &'a [Meta], owner: &mut Option<(&'a str, &'a str)>) -> bool {
+            for meta in metas {
+                let entry = (meta.owner.user(), meta.owner.group());
+                match owner {
+                    Some(owner) if *owner != entry => return false,
+                    Some(_) => (),
+                    None => *owner = Some(entry),
+                }
+
+                if let Some(content) = &meta.content {
+                    if !collect(content, owner) {
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+
+        let mut owner = None;
+        if collect(metas, &mut owner) {
+            owner.map(|(user, group)| (user.to_string(), group.to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+/// Encode the given bytes as standard base64 with padding, as required by the OSC 52
+/// clipboard sequence.
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity((input.len() + 2) / 3 * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let group = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+        output.push(ALPHABET[(group >> 18) as usize & 0x3f] as char);
+        output.push(ALPHABET[(group >> 12) as usize & 0x3f] as char);
+        output.push(if chunk.len() > 1 {
+            ALPHABET[(group >> 6) as usize & 0x3f] as char
+        } else {
+            '='
+        });
+        output.push(if chunk.len() > 2 {
+            ALPHABET[group as usize & 0x3f] as char
+        } else {
+            '='
+        });
+    }
+
+    output
+}
+
+/// Get the total and the available space of the filesystem holding the given path, in bytes.
+#[cfg(unix)]
+fn filesystem_space(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+
+    let total = stats.f_blocks as u64 * stats.f_frsize as u64;
+    let free = stats.f_bavail as u64 * stats.f_frsize as u64;
+    Some((total, free))
+}
+
+/// The windows equivalent would be GetDiskFreeSpaceEx; until that is wired up the footer is
+/// simply omitted there.
+#[cfg(not(unix))]
+fn filesystem_space(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// The chain of a path's ancestors ending in the path itself, from the root down.
+fn parent_chain(path: &Path) -> Vec<PathBuf> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    let mut chain: Vec<PathBuf> = path.ancestors().map(Path::to_path_buf).collect();
+    chain.reverse();
+    chain
+}
+
+/// The key under which a path argument is deduplicated. The directories leading to the entry
+/// are resolved, but the entry itself is kept as given: a symlink stays distinct from its
+/// target, and its trailing-slash spelling stays distinct again since that lists the target.
+fn dedup_key(path: &Path) -> std::ffi::OsString {
+    let mut key = match path.file_name() {
+        Some(name) => {
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            let mut key = parent
+                .canonicalize()
+                .unwrap_or_else(|_| parent.to_path_buf());
+            key.push(name);
+            key
+        }
+        // Paths like `.`, `..` or `/` have no final component to preserve.
+        None => path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+    };
+
+    // The metadata of the key is checked instead of the argument, because the operating
+    // system already resolves a trailing-slash spelling of a symlink to its target.
+    let is_symlink = key
+        .symlink_metadata()
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false);
+    if is_symlink && path.as_os_str().to_string_lossy().ends_with('/') {
+        key.push("/");
+    }
+
+    key.into_os_string()
+}
+
+/// Get the filesystem type and the mount options of the mount holding the given path, by
+/// picking the longest mount point containing it in `/proc/mounts`.
+#[cfg(target_os = "linux")]
+fn mount_of(path: &Path) -> Option<(String, String)> {
+    let path = path.canonicalize().ok()?;
+    let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+
+    let mut best: Option<(&str, &str, &str)> = None;
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace();
+        if let (Some(_), Some(point), Some(fstype), Some(options)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        {
+            if path.starts_with(point)
+                && best
+                    .map(|(best, _, _)| best.len() < point.len())
+                    .unwrap_or(true)
+            {
+                best = Some((point, fstype, options));
+            }
+        }
+    }
+
+    best.map(|(_, fstype, options)| (fstype.to_string(), options.to_string()))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mount_of(_path: &Path) -> Option<(String, String)> {
+    None
+}
+
+/// Whether the platform's filesystems are case insensitive by default, in which case names
+/// differing only by case clash on disk.
+fn case_insensitive_filesystem() -> bool {
+    cfg!(any(windows, target_os = "macos"))
+}
+
+/// Warn about names within the same directory which differ only by case. On a case
+/// insensitive filesystem such pairs clash when the tree is checked out or copied onto it.
+fn warn_case_clashes(metas: &[Meta]) {
+    use std::collections::HashMap;
+
+    let mut by_lowercase: HashMap<&str, &Meta> = HashMap::new();
+    for meta in metas {
+        if let Some(first) = by_lowercase.get(meta.name.lowercase()) {
+            print_error!(
+                "lsd: {}: name differs only by case from {}\n",
+                meta.path.display(),
+                first.path.display()
+            );
+        } else {
+            by_lowercase.insert(meta.name.lowercase(), meta);
+        }
+
+        if let Some(content) = &meta.content {
+            warn_case_clashes(content);
+        }
+    }
+}
+
+/// Warn when the filesystem holding the given path does not maintain precise access times,
+/// since the accessed-age block is misleading on `noatime` and `relatime` mounts.
+fn warn_coarse_atime(path: &Path) {
+    if let Some((_, options)) = mount_of(path) {
+        for option in options.split(',') {
+            if option == "noatime" || option == "relatime" {
+                print_error!(
+                    "lsd: the filesystem is mounted with {}, accessed-age may overstate how \
+                     long ago files were read\n",
+                    option
+                );
+            }
+        }
+    }
+}
+
+/// Check whether the terminal is known to lack the fonts for the icon glyphs, so `--icon
+/// auto` can degrade to plain names instead of printing replacement characters. This covers
+/// the Linux console and dumb terminals, which only render their built-in fonts, and CI logs,
+/// which are read in places where the fonts are unlikely to be installed.
+fn terminal_lacks_icons() -> bool {
+    if std::env::var_os("CI").is_some() {
+        return true;
+    }
+
+    match std::env::var("TERM") {
+        Ok(term) => term == "linux" || term == "dumb" || term.starts_with("vt"),
+        Err(_) => false,
+    }
+}
+
+/// Check whether the terminal reports a light background through the `COLORFGBG` environment
+/// variable, which holds the foreground and background color numbers separated by semicolons.
+fn has_light_background() -> bool {
+    match std::env::var("COLORFGBG") {
+        Ok(colors) => match colors.rsplit(';').next().map(str::parse::<u8>) {
+            // The same heuristic as vim uses: white and the light half of the classic
+            // sixteen color palette count as a light background.
+            Some(Ok(background)) => background == 7 || background >= 9,
+            _ => false,
+        },
+        Err(_) => false,
     }
 }
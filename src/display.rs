@@ -1,5 +1,5 @@
-use crate::color::{ColoredString, Colors};
-use crate::flags::{Block, Display, Flags, Layout};
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Block, Display, Flags, HyperlinkOption, Layout, Print0Field};
 use crate::icon::Icons;
 use crate::meta::name::DisplayOption;
 use crate::meta::{FileType, Meta};
@@ -7,7 +7,10 @@ use ansi_term::{ANSIString, ANSIStrings};
 use std::collections::HashMap;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The child count from which a directory's name gets the `dir.crowded` theme color.
+const CROWDED_DIR_THRESHOLD: usize = 1000;
 
 const EDGE: &str = "\u{251c}\u{2500}\u{2500}"; // "├──"
 const LINE: &str = "\u{2502}  "; // "├  "
@@ -20,7 +23,11 @@ pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
         None => None,
     };
 
+    // One buffer for the whole listing, so the recursion appends instead of concatenating
+    // intermediate strings, and the caller can flush it in a single write.
+    let mut output = String::new();
     inner_display_grid(
+        &mut output,
         &DisplayOption::None,
         metas,
         &flags,
@@ -28,14 +35,19 @@ pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
         icons,
         0,
         term_width,
-    )
+    );
+    output
 }
 
 pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
-    inner_display_tree(metas, &flags, colors, icons, 0, "")
+    let mut output = String::new();
+    inner_display_tree(&mut output, metas, &flags, colors, icons, 0, "");
+    output
 }
 
+#[allow(clippy::too_many_arguments)]
 fn inner_display_grid(
+    output: &mut String,
     display_option: &DisplayOption,
     metas: &[Meta],
     flags: &Flags,
@@ -43,9 +55,7 @@ fn inner_display_grid(
     icons: &Icons,
     depth: usize,
     term_width: Option<usize>,
-) -> String {
-    let mut output = String::new();
-
+) {
     let padding_rules = get_padding_rules(&metas, flags);
     let mut grid = match flags.layout {
         Layout::OneLine => Grid::new(GridOptions {
@@ -63,14 +73,38 @@ fn inner_display_grid(
     // asked to display the directory itself (rather than its contents).
     let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryItself);
 
-    // print the files first.
-    for meta in metas {
-        // Maybe skip showing the directory meta now; show its contents later.
-        if skip_dirs
-            && (matches!(meta.file_type, FileType::Directory{..})
+    // Maybe skip showing the directory meta now; show its contents later.
+    let is_skipped = |meta: &Meta| {
+        skip_dirs
+            && (matches!(meta.file_type, FileType::Directory { .. })
                 || (matches!(meta.file_type, FileType::SymLink { is_dir: true })
                     && flags.layout != Layout::OneLine))
-        {
+    };
+
+    // The caption row only lines up when every row holds every block, so it is limited to
+    // the one-line layouts. Levels whose entries are all deferred get no caption row either,
+    // and a lone name column is self-explanatory enough to not caption at all.
+    let caption_row = flags.header.0
+        && flags.layout != Layout::Grid
+        && !flags.raw.0
+        && flags.blocks.0.len() > 1
+        && metas.iter().any(|meta| !is_skipped(meta));
+    if caption_row {
+        for block in flags.blocks.0.iter() {
+            let caption = colors
+                .colorize(flags.header_captions.caption(block), &Elem::NoAccess)
+                .to_string();
+
+            grid.add(Cell {
+                width: get_visible_width(&caption),
+                contents: caption,
+            });
+        }
+    }
+
+    // print the files first.
+    for meta in metas {
+        if is_skipped(meta) {
             continue;
         }
 
@@ -83,6 +117,13 @@ fn inner_display_grid(
             &padding_rules,
         );
 
+        if flags.raw.0 {
+            let blocks: Vec<String> = blocks.iter().map(|block| block.to_string()).collect();
+            *output += &blocks.join(" ");
+            output.push('\n');
+            continue;
+        }
+
         for block in blocks {
             let block_str = block.to_string();
 
@@ -93,21 +134,49 @@ fn inner_display_grid(
         }
     }
 
-    if flags.layout == Layout::Grid {
+    if flags.raw.0 {
+        // The raw mode already streamed its lines above; skip the grid entirely.
+    } else if flags.layout == Layout::Grid {
         if let Some(tw) = term_width {
             if let Some(gridded_output) = grid.fit_into_width(tw) {
-                output += &gridded_output.to_string();
+                *output += &gridded_output.to_string();
             } else {
                 //does not fit into grid, usually because (some) filename(s)
                 //are longer or almost as long as term_width
                 //print line by line instead!
-                output += &grid.fit_into_columns(1).to_string();
+                *output += &grid.fit_into_columns(1).to_string();
             }
         } else {
-            output += &grid.fit_into_columns(1).to_string();
+            *output += &grid.fit_into_columns(1).to_string();
         }
     } else {
-        output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        let gridded = grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        if flags.extended.0 {
+            // Each non-skipped meta maps to exactly one grid row here, so the extended
+            // attribute lines can be interleaved beneath their entries.
+            let mut lines = gridded.lines();
+            if caption_row {
+                if let Some(line) = lines.next() {
+                    *output += line;
+                    output.push('\n');
+                }
+            }
+            for meta in metas.iter().filter(|meta| !is_skipped(meta)) {
+                if let Some(line) = lines.next() {
+                    *output += line;
+                    output.push('\n');
+                }
+                for (name, value) in meta.xattrs.entries(&meta.path) {
+                    *output += &format!(
+                        "  {}: {}\n",
+                        colors.colorize(name, &Elem::NoAccess),
+                        value
+                    );
+                }
+            }
+        } else {
+            *output += &gridded;
+        }
     }
 
     let should_display_folder_path = should_display_folder_path(depth, &metas, &flags);
@@ -116,14 +185,15 @@ fn inner_display_grid(
     for meta in metas {
         if meta.content.is_some() {
             if should_display_folder_path {
-                output += &display_folder_path(&meta);
+                *output += &display_folder_path(&meta);
             }
 
             let display_option = DisplayOption::Relative {
                 base_path: &meta.path,
             };
 
-            output += &inner_display_grid(
+            inner_display_grid(
+                output,
                 &display_option,
                 meta.content.as_ref().unwrap(),
                 &flags,
@@ -134,19 +204,49 @@ fn inner_display_grid(
             );
         }
     }
+}
 
+/// Render the listing as machine readable records: the selected fields separated by the unit
+/// separator (0x1f), each record terminated by NUL. A middle ground between the visual
+/// layouts and the JSON output for shell pipelines.
+pub fn print0(metas: &[Meta], flags: &Flags) -> String {
+    let mut output = String::new();
+    append_print0(&mut output, metas, flags);
     output
 }
 
+fn append_print0(output: &mut String, metas: &[Meta], flags: &Flags) {
+    for meta in metas {
+        for (index, field) in flags.print0_fields.0.iter().enumerate() {
+            if index > 0 {
+                output.push('\u{1f}');
+            }
+
+            match field {
+                Print0Field::Name => *output += &meta.name.name,
+                Print0Field::Path => *output += &meta.path.to_string_lossy(),
+                Print0Field::Size => *output += &meta.size.get_bytes().to_string(),
+                Print0Field::Mtime => *output += &meta.date.unix_timestamp().to_string(),
+                Print0Field::Type => *output += crate::json::type_name(&meta.file_type),
+            }
+        }
+        output.push('\0');
+
+        if let Some(content) = &meta.content {
+            append_print0(output, content, flags);
+        }
+    }
+}
+
 fn inner_display_tree(
+    output: &mut String,
     metas: &[Meta],
     flags: &Flags,
     colors: &Colors,
     icons: &Icons,
     depth: usize,
     prefix: &str,
-) -> String {
-    let mut output = String::new();
+) {
     let last_idx = metas.len();
 
     let padding_rules = get_padding_rules(&metas, flags);
@@ -181,31 +281,38 @@ fn inner_display_tree(
         let is_last_folder_elem = idx + 1 != last_idx;
 
         if depth > 0 {
-            output += prefix;
+            *output += prefix;
 
-            if is_last_folder_elem {
-                output += EDGE;
+            if let Some(width) = flags.tree_indent.0 {
+                *output += &" ".repeat(width);
             } else {
-                output += CORNER;
+                if is_last_folder_elem {
+                    *output += EDGE;
+                } else {
+                    *output += CORNER;
+                }
+                *output += " ";
             }
-            output += " ";
         }
 
-        output += &String::from(lines.next().unwrap());
-        output += "\n";
+        *output += lines.next().unwrap();
+        output.push('\n');
 
         if meta.content.is_some() {
             let mut new_prefix = String::from(prefix);
 
             if depth > 0 {
-                if is_last_folder_elem {
+                if let Some(width) = flags.tree_indent.0 {
+                    new_prefix += &" ".repeat(width);
+                } else if is_last_folder_elem {
                     new_prefix += LINE;
                 } else {
                     new_prefix += BLANK;
                 }
             }
 
-            output += &inner_display_tree(
+            inner_display_tree(
+                output,
                 &meta.content.as_ref().unwrap(),
                 &flags,
                 colors,
@@ -215,8 +322,6 @@ fn inner_display_tree(
             );
         }
     }
-
-    output
 }
 
 fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
@@ -253,20 +358,40 @@ fn get_output<'a>(
     display_option: &DisplayOption,
     padding_rules: &HashMap<Block, usize>,
 ) -> Vec<ANSIString<'a>> {
+    if let Some(error) = &meta.error {
+        let mut strings = placeholder_output(meta, error, colors, icons, flags, display_option);
+        if flags.strict_reset.0 {
+            isolate_cell_styles(&mut strings);
+        }
+        return strings;
+    }
+
     let mut strings: Vec<ANSIString> = Vec::new();
     for block in flags.blocks.0.iter() {
         match block {
             Block::INode => strings.push(meta.inode.render(colors)),
+            Block::Links => {
+                strings.push(meta.links.render(colors, &flags, padding_rules[&Block::Links]))
+            }
+            Block::Context => strings.push(meta.xattrs.render_context(&meta.path, colors)),
+            Block::Access => strings.push(meta.render_access(colors, &flags)),
             Block::Permission => {
-                let s: &[ColoredString] = &[
+                let mut parts = vec![
                     meta.file_type.render(colors),
-                    meta.permissions.render(colors),
+                    meta.permissions.render(
+                        colors,
+                        &flags,
+                        matches!(meta.file_type, FileType::Directory { .. }),
+                    ),
                 ];
-                let res = ANSIStrings(s).to_string();
+                if let Some(suffix) = meta.xattrs.render_suffix(colors) {
+                    parts.push(suffix);
+                }
+                let res = ANSIStrings(&parts).to_string();
                 strings.push(ColoredString::from(res));
             }
-            Block::User => strings.push(meta.owner.render_user(colors)),
-            Block::Group => strings.push(meta.owner.render_group(colors)),
+            Block::User => strings.push(meta.owner.render_user(colors, &flags)),
+            Block::Group => strings.push(meta.owner.render_group(colors, &flags)),
             Block::Size => strings.push(meta.size.render(
                 colors,
                 &flags,
@@ -274,32 +399,207 @@ fn get_output<'a>(
             )),
             Block::SizeValue => strings.push(meta.size.render_value(colors, flags)),
             Block::Date => strings.push(meta.date.render(colors, &flags)),
+            Block::BirthDate => strings.push(match &meta.created {
+                Some(created) => created.render(colors, &flags),
+                None => colors.colorize(String::from("-"), &Elem::Older),
+            }),
+            Block::AccessedAge => strings.push(match &meta.accessed {
+                Some(accessed) => accessed.render_age(colors),
+                None => colors.colorize(String::from("-"), &Elem::Older),
+            }),
+            Block::GitStatus => strings.push(match &meta.git_status {
+                Some(status) => status.render(colors),
+                None => colors.colorize(String::from("-"), &Elem::NoAccess),
+            }),
+            Block::TypeIcon => strings.push(meta.name.render_icon(colors, icons)),
             Block::Name => {
-                let s: String =
-                    if flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid {
-                        ANSIStrings(&[
-                            meta.name.render(colors, icons, &display_option),
-                            meta.indicator.render(&flags),
-                        ])
-                        .to_string()
-                    } else {
-                        ANSIStrings(&[
-                            meta.name.render(colors, icons, &display_option),
-                            meta.indicator.render(&flags),
-                            meta.symlink.render(colors),
-                        ])
-                        .to_string()
-                    };
-
-                strings.push(ColoredString::from(s));
+                let mut name = if flags.crowded_dirs.0 && is_crowded_dir(meta) {
+                    meta.name
+                        .render_as(colors, icons, &display_option, &Elem::CrowdedDir)
+                } else {
+                    meta.name.render(colors, icons, &display_option)
+                };
+                if flags.hyperlink == HyperlinkOption::Always {
+                    name = hyperlink(name, &meta.path);
+                }
+
+                let mut parts = vec![name, meta.indicator.render(&flags)];
+
+                if let Some(badge) = meta.render_lock_badge(colors) {
+                    parts.push(badge);
+                }
+
+                if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
+                    match flags.link_chain.0 {
+                        Some(depth) => {
+                            parts.push(meta.symlink.render_chain(&meta.path, colors, depth))
+                        }
+                        None => parts.push(meta.symlink.render(colors)),
+                    }
+                }
+
+                if flags.peers.0 {
+                    if let Some(peers) = meta.render_peers(colors) {
+                        parts.push(peers);
+                    }
+                }
+
+                if flags.extension_stats.0 {
+                    if let Some(stats) = meta.render_extension_stats(colors) {
+                        parts.push(stats);
+                    }
+                }
+
+                if flags.units.0 {
+                    if let Some(unit) = meta.render_unit(colors) {
+                        parts.push(unit);
+                    }
+                }
+
+                if flags.bit_hints.0 {
+                    if let Some(hints) = meta.render_bit_hints(colors) {
+                        parts.push(hints);
+                    }
+                }
+
+                strings.push(ColoredString::from(ANSIStrings(&parts).to_string()));
             }
         };
+
+        if let Some(&max_width) = flags.max_widths.0.get(block) {
+            let rendered = strings.last().unwrap().to_string();
+            if get_visible_width(&rendered) > max_width {
+                *strings.last_mut().unwrap() =
+                    ColoredString::from(truncate_to_width(&rendered, max_width));
+            }
+        }
+    }
+
+    if flags.strict_reset.0 {
+        isolate_cell_styles(&mut strings);
     }
 
     strings
 }
 
+/// Rewrite each rendered cell so its style cannot bleed into the surrounding output: trailing
+/// spaces are moved after the closing escape, and a full reset is appended when the cell is
+/// styled. Some terminals and tmux erase with the current background color, so a styled cell
+/// ending in padding can paint far beyond its own width without this.
+fn isolate_cell_styles(strings: &mut [ANSIString]) {
+    for string in strings.iter_mut() {
+        let rendered = string.to_string();
+        if !rendered.contains('\u{1b}') {
+            continue;
+        }
+
+        // Peel trailing resets and spaces off, so the padding ends up after one final reset.
+        let mut core = rendered.as_str();
+        let mut spaces = 0;
+        loop {
+            if let Some(rest) = core.strip_suffix(' ') {
+                core = rest;
+                spaces += 1;
+            } else if let Some(rest) = core.strip_suffix("\u{1b}[0m") {
+                core = rest;
+            } else {
+                break;
+            }
+        }
+
+        let mut output = core.to_string();
+        output.push_str("\u{1b}[0m");
+        for _ in 0..spaces {
+            output.push(' ');
+        }
+
+        *string = ColoredString::from(output);
+    }
+}
+
+/// Whether the entry is a directory holding at least [CROWDED_DIR_THRESHOLD] entries. The
+/// content gathered by recursion is used when present; otherwise the directory is scanned
+/// once, which only reads the entry names and stays far cheaper than a listing.
+fn is_crowded_dir(meta: &Meta) -> bool {
+    if !matches!(meta.file_type, FileType::Directory { .. }) {
+        return false;
+    }
+
+    match &meta.content {
+        Some(content) => content.len() >= CROWDED_DIR_THRESHOLD,
+        None => match std::fs::read_dir(&meta.path) {
+            Ok(entries) => entries.take(CROWDED_DIR_THRESHOLD).count() >= CROWDED_DIR_THRESHOLD,
+            Err(_) => false,
+        },
+    }
+}
+
+/// Render a row for an entry whose metadata could not be read: the name with a dimmed error
+/// note, `?` for the identity blocks and `-` for everything else.
+fn placeholder_output<'a>(
+    meta: &'a Meta,
+    error: &str,
+    colors: &'a Colors,
+    icons: &'a Icons,
+    flags: &'a Flags,
+    display_option: &DisplayOption,
+) -> Vec<ANSIString<'a>> {
+    let mut strings: Vec<ANSIString> = Vec::new();
+    for block in flags.blocks.0.iter() {
+        match block {
+            Block::Permission => {
+                strings.push(colors.colorize(String::from("?????????"), &Elem::NoAccess))
+            }
+            Block::User | Block::Group | Block::Access => {
+                strings.push(colors.colorize(String::from("?"), &Elem::NoAccess))
+            }
+            Block::Name => {
+                let parts = &[
+                    meta.name.render(colors, icons, &display_option),
+                    colors.colorize(format!(" ({})", error), &Elem::NoAccess),
+                ];
+                strings.push(ColoredString::from(ANSIStrings(parts).to_string()));
+            }
+            _ => strings.push(colors.colorize(String::from("-"), &Elem::NoAccess)),
+        }
+    }
+
+    strings
+}
+
+/// Truncate the visible part of `input` to `width` columns, ending in an ellipsis. The ANSI
+/// escape sequences within are preserved, so styled content stays terminated correctly.
+fn truncate_to_width(input: &str, width: usize) -> String {
+    let mut output = String::new();
+    let mut visible = 0;
+    let mut in_escape = false;
+
+    for chr in input.chars() {
+        if in_escape {
+            output.push(chr);
+            if chr == 'm' {
+                in_escape = false;
+            }
+        } else if chr == '\u{1b}' {
+            output.push(chr);
+            in_escape = true;
+        } else if visible + UnicodeWidthChar::width(chr).unwrap_or(0) < width {
+            visible += UnicodeWidthChar::width(chr).unwrap_or(0);
+            output.push(chr);
+        }
+    }
+
+    output.push('\u{2026}');
+    output
+}
+
 fn get_visible_width(input: &str) -> usize {
+    // Without any escape sequence the width is the unicode width; this is the common case in
+    // large uncolored listings, so skip the escape scan entirely.
+    if !input.contains('\u{1b}') {
+        return UnicodeWidthStr::width(input);
+    }
+
     let mut nb_invisible_char = 0;
 
     // If the input has color, do not compute the length contributed by the color to the actual length
@@ -312,14 +612,60 @@ fn get_visible_width(input: &str) -> usize {
         }
     }
 
+    // The OSC sequences of the hyperlinks do not print either; everything up to and
+    // including the string terminator stays invisible.
+    for (idx, _) in input.match_indices("\u{1b}]") {
+        let (_, s) = input.split_at(idx);
+
+        if let Some(len) = s.find("\u{1b}\\") {
+            nb_invisible_char += len
+        }
+    }
+
     UnicodeWidthStr::width(input) - nb_invisible_char
 }
 
+/// Wrap an already rendered name in an OSC 8 hyperlink, so supporting terminals make it
+/// clickable.
+fn hyperlink<'a>(content: ANSIString<'a>, path: &std::path::Path) -> ANSIString<'a> {
+    ANSIString::from(format!(
+        "\u{1b}]8;;{}\u{1b}\\{}\u{1b}]8;;\u{1b}\\",
+        file_url(path),
+        content
+    ))
+}
+
+/// The `file://` URL for a path, with everything outside the unreserved characters
+/// percent-encoded.
+fn file_url(path: &std::path::Path) -> String {
+    let absolute = path
+        .canonicalize()
+        .unwrap_or_else(|_| std::env::current_dir().unwrap_or_default().join(path));
+
+    let mut url = String::from("file://");
+    for byte in absolute.to_string_lossy().bytes() {
+        match byte {
+            b'/' | b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                url.push(byte as char)
+            }
+            other => url.push_str(&format!("%{:02X}", other)),
+        }
+    }
+
+    url
+}
+
 fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
     let mut max_value_length: usize = 0;
 
     for meta in metas {
-        let value_len = meta.size.value_string(flags).len();
+        let value_string = meta.size.value_string(flags);
+        let value_len = if flags.size_align.0 {
+            // Only the integer part takes part in the alignment.
+            value_string.split('.').next().unwrap_or_default().len()
+        } else {
+            value_string.len()
+        };
 
         if value_len > max_value_length {
             max_value_length = value_len;
@@ -338,6 +684,16 @@ fn get_padding_rules(metas: &[Meta], flags: &Flags) -> HashMap<Block, usize> {
         padding_rules.insert(Block::SizeValue, size_val);
     }
 
+    if flags.blocks.0.contains(&Block::Links) {
+        let links_val = metas
+            .iter()
+            .map(|meta| meta.links.value_string().len())
+            .max()
+            .unwrap_or_default();
+
+        padding_rules.insert(Block::Links, links_val);
+    }
+
     padding_rules
 }
 
@@ -371,7 +727,7 @@ mod tests {
                 },
             );
             let output = name.render(
-                &Colors::new(color::Theme::NoColor),
+                &Colors::new(color::Theme::NoColor, color::Palette::Default),
                 &Icons::new(icon::Theme::NoIcon),
                 &DisplayOption::FileName,
             );
@@ -403,7 +759,7 @@ mod tests {
             );
             let output = name
                 .render(
-                    &Colors::new(color::Theme::NoColor),
+                    &Colors::new(color::Theme::NoColor, color::Palette::Default),
                     &Icons::new(icon::Theme::Fancy),
                     &DisplayOption::FileName,
                 )
@@ -435,7 +791,7 @@ mod tests {
             );
             let output = name
                 .render(
-                    &Colors::new(color::Theme::NoLscolors),
+                    &Colors::new(color::Theme::NoLscolors, color::Palette::Default),
                     &Icons::new(icon::Theme::NoIcon),
                     &DisplayOption::FileName,
                 )
@@ -471,7 +827,7 @@ mod tests {
             );
             let output = name
                 .render(
-                    &Colors::new(color::Theme::NoColor),
+                    &Colors::new(color::Theme::NoColor, color::Palette::Default),
                     &Icons::new(icon::Theme::NoIcon),
                     &DisplayOption::FileName,
                 )
@@ -0,0 +1,95 @@
+//! This module renders a listing of [Meta] as a Graphviz digraph, so diagrams of directory
+//! layouts can be generated straight from the listing. Every entry becomes a node shaped by
+//! its type and scaled by its size, and every directory points to the entries below it.
+//!
+//! The output is a complete `digraph` ready for `dot -Tsvg`.
+
+use crate::meta::{FileType, Meta};
+
+/// Render the given metas as a Graphviz digraph, one node per visited entry.
+pub fn render(metas: &[Meta]) -> String {
+    let mut output = String::from("digraph tree {\n");
+    output += "    rankdir=LR;\n";
+    output += "    node [fontname=\"monospace\"];\n";
+
+    let mut next_id = 0;
+    for meta in metas {
+        append_node(&mut output, meta, None, &mut next_id);
+    }
+
+    output += "}\n";
+    output
+}
+
+fn append_node(output: &mut String, meta: &Meta, parent: Option<usize>, next_id: &mut usize) {
+    let id = *next_id;
+    *next_id += 1;
+
+    output.push_str(&format!(
+        "    n{} [label={}, shape={}, fontsize={}];\n",
+        id,
+        escape(&meta.name.name),
+        shape(&meta.file_type),
+        font_size(meta)
+    ));
+
+    if let Some(parent) = parent {
+        output.push_str(&format!("    n{} -> n{};\n", parent, id));
+    }
+
+    if let Some(content) = &meta.content {
+        for child in content {
+            append_node(output, child, Some(id), next_id);
+        }
+    }
+}
+
+/// The node shape for each entry type. Directories stand out as folders, symlinks as
+/// arrow-like cds, and everything unusual as a diamond.
+fn shape(file_type: &FileType) -> &'static str {
+    match file_type {
+        FileType::Directory { .. } => "folder",
+        FileType::File { .. } => "note",
+        FileType::SymLink { .. } => "cds",
+        _ => "diamond",
+    }
+}
+
+/// The font size for an entry, growing with the order of magnitude of its size so the big
+/// files stand out without dwarfing the rest of the graph.
+fn font_size(meta: &Meta) -> u32 {
+    let mut magnitude = 0;
+    let mut bytes = meta.size.get_bytes();
+    while bytes >= 10 {
+        bytes /= 10;
+        magnitude += 1;
+    }
+
+    14 + 2 * magnitude
+}
+
+/// Quote and escape a string for use as a Graphviz attribute value.
+fn escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len() + 2);
+    escaped.push('"');
+    for character in input.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::escape;
+
+    #[test]
+    fn test_escape_quotes() {
+        assert_eq!("\"a\\\"b\\\\c\"", escape("a\"b\\c"));
+    }
+}
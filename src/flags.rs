@@ -1,39 +1,156 @@
+pub mod bit_hints;
 pub mod blocks;
+pub mod case_check;
+pub mod check_access;
 pub mod color;
+pub mod collapse_globs;
+pub mod collapse_owner;
+pub mod color_overrides;
+pub mod color_source;
+pub mod contrast;
+pub mod copy_paths;
+pub mod count;
+pub mod crowded_dirs;
 pub mod date;
+pub mod date_field;
+pub mod date_granularity;
 pub mod dereference;
+pub mod disk_usage;
+pub mod deterministic;
 pub mod display;
+pub mod expect_mode;
+pub mod extended;
+pub mod extension_stats;
+pub mod fast_network_fs;
+pub mod follow_links;
+pub mod foreign_owner;
+pub mod git;
+pub mod git_ignore;
+pub mod header;
+pub mod hyperlink;
 pub mod icons;
+pub mod ignore_file;
 pub mod ignore_globs;
 pub mod indicators;
+pub mod json;
+pub mod keep_arg_order;
+pub mod keep_duplicates;
 pub mod layout;
+pub mod link_chain;
+pub mod max_widths;
+pub mod mount_info;
+pub mod no_external;
+pub mod output_format;
+pub mod padding;
+pub mod parents;
+pub mod peers;
+pub mod permission;
+pub mod print0_fields;
+pub mod raw;
 pub mod recursion;
+pub mod resolve;
+pub mod sids;
 pub mod size;
+pub mod size_source;
+pub mod size_unit;
+pub mod size_align;
 pub mod sorting;
+pub mod stdin;
+pub mod strict_reset;
 pub mod symlinks;
+pub mod summary;
+pub mod theme;
+pub mod theme_preview;
+pub mod time_precision;
+pub mod top;
 pub mod total_size;
+pub mod tree_indent;
+pub mod uid_map;
+pub mod umask_check;
+pub mod units;
+pub mod windows_attributes;
 
+pub use bit_hints::BitHints;
 pub use blocks::Block;
 pub use blocks::Blocks;
+pub use case_check::CaseCheck;
+pub use check_access::CheckAccess;
 pub use color::Color;
 pub use color::ColorOption;
+pub use collapse_globs::CollapseGlobs;
+pub use collapse_owner::CollapseOwner;
+pub use color_overrides::ColorOverrides;
+pub use color_source::ColorSource;
+pub use contrast::Contrast;
+pub use copy_paths::CopyPaths;
+pub use count::Count;
+pub use crowded_dirs::CrowdedDirs;
 pub use date::DateFlag;
+pub use date_field::DateField;
+pub use date_granularity::DateGranularity;
 pub use dereference::Dereference;
+pub use disk_usage::DiskUsage;
+pub use deterministic::Deterministic;
 pub use display::Display;
+pub use expect_mode::ExpectMode;
+pub use extended::Extended;
+pub use extension_stats::ExtensionStats;
+pub use fast_network_fs::FastNetworkFs;
+pub use follow_links::FollowLinksAtDepth;
+pub use foreign_owner::ForeignOwner;
+pub use git::GitFlag;
+pub use git_ignore::GitIgnore;
+pub use header::Header;
+pub use header::HeaderCaptions;
+pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
+pub use icons::IconOverrides;
 pub use icons::IconTheme;
 pub use icons::Icons;
+pub use ignore_file::IgnoreFile;
 pub use ignore_globs::IgnoreGlobs;
 pub use indicators::Indicators;
+pub use json::Json;
+pub use keep_arg_order::KeepArgOrder;
+pub use keep_duplicates::KeepDuplicates;
 pub use layout::Layout;
+pub use link_chain::LinkChain;
+pub use max_widths::MaxWidths;
+pub use mount_info::MountInfo;
+pub use no_external::NoExternal;
+pub use output_format::OutputFormat;
+pub use padding::NumericPadding;
+pub use parents::Parents;
+pub use peers::Peers;
+pub use permission::PermissionFlag;
+pub use print0_fields::Print0Field;
+pub use print0_fields::Print0Fields;
+pub use raw::Raw;
 pub use recursion::Recursion;
+pub use resolve::Resolve;
+pub use sids::Sids;
 pub use size::SizeFlag;
+pub use size_source::SizeSource;
+pub use size_unit::SizeUnitFlag;
+pub use size_align::SizeAlign;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use stdin::Stdin;
+pub use strict_reset::StrictReset;
 pub use symlinks::NoSymlink;
+pub use summary::Summary;
+pub use theme::ThemeFlag;
+pub use theme_preview::ThemePreview;
+pub use time_precision::TimePrecision;
+pub use top::Top;
 pub use total_size::TotalSize;
+pub use tree_indent::TreeIndent;
+pub use uid_map::UidMap;
+pub use umask_check::UmaskCheck;
+pub use units::Units;
+pub use windows_attributes::WindowsAttributes;
 
 use crate::config_file::Config;
 
@@ -45,20 +162,79 @@ use yaml_rust::Yaml;
 /// A struct to hold all set configuration flags for the application.
 #[derive(Clone, Debug, Default)]
 pub struct Flags {
+    pub bit_hints: BitHints,
     pub blocks: Blocks,
+    pub case_check: CaseCheck,
+    pub check_access: CheckAccess,
+    pub collapse_globs: CollapseGlobs,
+    pub collapse_owner: CollapseOwner,
     pub color: Color,
+    pub color_overrides: ColorOverrides,
+    pub color_source: ColorSource,
+    pub contrast: Contrast,
+    pub copy_paths: CopyPaths,
+    pub count: Count,
+    pub crowded_dirs: CrowdedDirs,
     pub date: DateFlag,
+    pub date_field: DateField,
+    pub date_granularity: DateGranularity,
     pub dereference: Dereference,
+    pub deterministic: Deterministic,
+    pub disk_usage: DiskUsage,
     pub display: Display,
     pub display_indicators: Indicators,
+    pub expect_mode: ExpectMode,
+    pub extended: Extended,
+    pub extension_stats: ExtensionStats,
+    pub fast_network_fs: FastNetworkFs,
+    pub follow_links_at_depth: FollowLinksAtDepth,
+    pub foreign_owner: ForeignOwner,
+    pub git_ignore: GitIgnore,
+    pub header: Header,
+    pub header_captions: HeaderCaptions,
+    pub hyperlink: HyperlinkOption,
     pub icons: Icons,
+    pub ignore_file: IgnoreFile,
     pub ignore_globs: IgnoreGlobs,
+    pub json: Json,
+    pub keep_arg_order: KeepArgOrder,
+    pub keep_duplicates: KeepDuplicates,
     pub layout: Layout,
+    pub link_chain: LinkChain,
+    pub max_widths: MaxWidths,
+    pub mount_info: MountInfo,
+    pub no_external: NoExternal,
     pub no_symlink: NoSymlink,
+    pub output_format: OutputFormat,
+    pub padding: NumericPadding,
+    pub parents: Parents,
+    pub peers: Peers,
+    pub permission: PermissionFlag,
+    pub print0_fields: Print0Fields,
+    pub raw: Raw,
     pub recursion: Recursion,
+    pub resolve: Resolve,
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub sids: Sids,
     pub size: SizeFlag,
+    pub size_source: SizeSource,
+    pub size_unit: SizeUnitFlag,
+    pub size_align: SizeAlign,
     pub sorting: Sorting,
+    pub stdin: Stdin,
+    pub strict_reset: StrictReset,
+    pub summary: Summary,
+    pub theme: ThemeFlag,
+    pub theme_preview: ThemePreview,
+    pub time_precision: TimePrecision,
+    pub top: Top,
     pub total_size: TotalSize,
+    pub tree_indent: TreeIndent,
+    pub uid_map: UidMap,
+    pub umask_check: UmaskCheck,
+    pub units: Units,
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub windows_attributes: WindowsAttributes,
 }
 
 impl Flags {
@@ -70,20 +246,77 @@ impl Flags {
     /// the recursion depth parameter fails.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
         Ok(Self {
+            bit_hints: BitHints::configure_from(matches, config),
             blocks: Blocks::configure_from(matches, config)?,
+            case_check: CaseCheck::configure_from(matches, config),
+            check_access: CheckAccess::configure_from(matches, config),
+            collapse_globs: CollapseGlobs::configure_from(matches, config)?,
+            collapse_owner: CollapseOwner::configure_from(matches, config),
             color: Color::configure_from(matches, config),
+            color_overrides: ColorOverrides::configure_from(matches, config),
+            color_source: ColorSource::configure_from(matches, config),
+            contrast: Contrast::configure_from(matches, config)?,
+            copy_paths: CopyPaths::configure_from(matches, config),
+            count: Count::configure_from(matches, config),
+            crowded_dirs: CrowdedDirs::configure_from(matches, config),
             date: DateFlag::configure_from(matches, config),
+            date_field: DateField::configure_from(matches, config),
+            date_granularity: DateGranularity::configure_from(matches, config),
             dereference: Dereference::configure_from(matches, config),
+            deterministic: Deterministic::configure_from(matches, config),
+            disk_usage: DiskUsage::configure_from(matches, config),
             display: Display::configure_from(matches, config),
+            json: Json::configure_from(matches, config),
+            keep_arg_order: KeepArgOrder::configure_from(matches, config),
+            keep_duplicates: KeepDuplicates::configure_from(matches, config),
             layout: Layout::configure_from(matches, config),
+            link_chain: LinkChain::configure_from(matches, config)?,
+            sids: Sids::configure_from(matches, config),
             size: SizeFlag::configure_from(matches, config),
+            size_source: SizeSource::configure_from(matches, config),
+            size_unit: SizeUnitFlag::configure_from(matches, config),
+            size_align: SizeAlign::configure_from(matches, config),
             display_indicators: Indicators::configure_from(matches, config),
+            expect_mode: ExpectMode::configure_from(matches, config)?,
+            extended: Extended::configure_from(matches, config),
+            extension_stats: ExtensionStats::configure_from(matches, config),
+            fast_network_fs: FastNetworkFs::configure_from(matches, config),
+            follow_links_at_depth: FollowLinksAtDepth::configure_from(matches, config)?,
+            foreign_owner: ForeignOwner::configure_from(matches, config),
+            git_ignore: GitIgnore::configure_from(matches, config),
+            header: Header::configure_from(matches, config),
+            header_captions: HeaderCaptions::configure_from(matches, config),
+            hyperlink: HyperlinkOption::configure_from(matches, config),
             icons: Icons::configure_from(matches, config),
+            ignore_file: IgnoreFile::configure_from(matches, config)?,
             ignore_globs: IgnoreGlobs::configure_from(matches, config)?,
+            max_widths: MaxWidths::configure_from(matches, config)?,
+            mount_info: MountInfo::configure_from(matches, config),
+            no_external: NoExternal::configure_from(matches, config),
             no_symlink: NoSymlink::configure_from(matches, config),
+            output_format: OutputFormat::configure_from(matches, config),
+            padding: NumericPadding::configure_from(matches, config),
+            parents: Parents::configure_from(matches, config),
+            peers: Peers::configure_from(matches, config),
+            permission: PermissionFlag::configure_from(matches, config),
+            print0_fields: Print0Fields::configure_from(matches, config)?,
+            raw: Raw::configure_from(matches, config),
             recursion: Recursion::configure_from(matches, config)?,
+            resolve: Resolve::configure_from(matches, config),
             sorting: Sorting::configure_from(matches, config),
+            stdin: Stdin::configure_from(matches, config),
+            strict_reset: StrictReset::configure_from(matches, config),
+            summary: Summary::configure_from(matches, config),
+            theme: ThemeFlag::configure_from(matches, config),
+            theme_preview: ThemePreview::configure_from(matches, config),
+            time_precision: TimePrecision::configure_from(matches, config),
+            top: Top::configure_from(matches, config)?,
             total_size: TotalSize::configure_from(matches, config),
+            tree_indent: TreeIndent::configure_from(matches, config)?,
+            uid_map: UidMap::configure_from(matches, config),
+            umask_check: UmaskCheck::configure_from(matches, config),
+            units: Units::configure_from(matches, config),
+            windows_attributes: WindowsAttributes::configure_from(matches, config),
         })
     }
 }
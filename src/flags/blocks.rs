@@ -3,6 +3,7 @@
 
 use std::convert::TryFrom;
 
+use super::{Configurable, GitFlag};
 use crate::config_file::Config;
 
 use clap::{ArgMatches, Error, ErrorKind};
@@ -58,6 +59,24 @@ impl Blocks {
             }
         }
 
+        if matches.is_present("check-access") {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_append_access();
+            }
+        }
+
+        if matches.is_present("context") {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_insert_context();
+            }
+        }
+
+        if GitFlag::configure_from(matches, config).0 {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_prepend_git_status();
+            }
+        }
+
         result
     }
 
@@ -167,6 +186,40 @@ impl Blocks {
             self.prepend_inode()
         }
     }
+
+    /// Appends a [Block] of variant [Access](Block::Access), if `self` does not already contain a
+    /// Block of that variant.
+    fn optional_append_access(&mut self) {
+        if !self.0.contains(&Block::Access) {
+            self.0.push(Block::Access)
+        }
+    }
+
+    /// Inserts a [Block] of variant [Context](Block::Context) after the group block like
+    /// `ls -Z`, if `self` does not already contain one. Without a group block it lands in
+    /// front of the name.
+    fn optional_insert_context(&mut self) {
+        if self.0.contains(&Block::Context) {
+            return;
+        }
+
+        let position = self
+            .0
+            .iter()
+            .position(|block| *block == Block::Group)
+            .map(|position| position + 1)
+            .or_else(|| self.0.iter().position(|block| *block == Block::Name))
+            .unwrap_or(self.0.len());
+        self.0.insert(position, Block::Context);
+    }
+
+    /// Prepends a [Block] of variant [GitStatus](Block::GitStatus), if `self` does not already
+    /// contain one, so the status column sits in front of the name like `git status -s`.
+    fn optional_prepend_git_status(&mut self) {
+        if !self.0.contains(&Block::GitStatus) {
+            self.0.insert(0, Block::GitStatus)
+        }
+    }
 }
 
 /// The default value for `Blocks` contains a [Vec] of [Name](Block::Name).
@@ -185,8 +238,15 @@ pub enum Block {
     Size,
     SizeValue,
     Date,
+    BirthDate,
+    AccessedAge,
     Name,
     INode,
+    Links,
+    Context,
+    Access,
+    GitStatus,
+    TypeIcon,
 }
 
 impl TryFrom<&str> for Block {
@@ -200,8 +260,15 @@ impl TryFrom<&str> for Block {
             "size" => Ok(Self::Size),
             "size_value" => Ok(Self::SizeValue),
             "date" => Ok(Self::Date),
+            "btime" => Ok(Self::BirthDate),
+            "accessed-age" => Ok(Self::AccessedAge),
             "name" => Ok(Self::Name),
             "inode" => Ok(Self::INode),
+            "links" => Ok(Self::Links),
+            "context" => Ok(Self::Context),
+            "access" => Ok(Self::Access),
+            "git" => Ok(Self::GitStatus),
+            "type-icon" => Ok(Self::TypeIcon),
             _ => Err(format!("Not a valid block name: {}", &string)),
         }
     }
@@ -293,6 +360,35 @@ mod test_blocks {
         assert_eq_ok!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_git_arg() {
+        let argv = vec!["lsd", "--git"];
+
+        let mut target_blocks = Blocks::default();
+        target_blocks.0.insert(0, Block::GitStatus);
+        let target = Ok::<_, Error>(target_blocks);
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_git_config() {
+        let argv = vec!["lsd"];
+
+        let mut target_blocks = Blocks::default();
+        target_blocks.0.insert(0, Block::GitStatus);
+        let target = Ok::<_, Error>(target_blocks);
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml = YamlLoader::load_from_str("git: true").unwrap()[0].clone();
+        let result = Blocks::configure_from(&matches, &Config::with_yaml(yaml));
+
+        assert_eq_ok!(result, target);
+    }
+
     #[test]
     fn test_configure_from_prepend_inode_without_long() {
         let argv = vec!["lsd", "--blocks", "permission", "--inode"];
@@ -535,4 +631,34 @@ mod test_block {
     fn test_inode() {
         assert_eq!(Ok(Block::INode), Block::try_from("inode"));
     }
+
+    #[test]
+    fn test_type_icon() {
+        assert_eq!(Ok(Block::TypeIcon), Block::try_from("type-icon"));
+    }
+
+    #[test]
+    fn test_btime() {
+        assert_eq!(Ok(Block::BirthDate), Block::try_from("btime"));
+    }
+
+    #[test]
+    fn test_accessed_age() {
+        assert_eq!(Ok(Block::AccessedAge), Block::try_from("accessed-age"));
+    }
+
+    #[test]
+    fn test_access() {
+        assert_eq!(Ok(Block::Access), Block::try_from("access"));
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(Ok(Block::Links), Block::try_from("links"));
+    }
+
+    #[test]
+    fn test_context() {
+        assert_eq!(Ok(Block::Context), Block::try_from("context"));
+    }
 }
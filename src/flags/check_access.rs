@@ -0,0 +1,154 @@
+//! This module defines the [CheckAccess] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag holding the account whose effective access rights should be displayed in the
+/// "access" block.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct CheckAccess(pub Option<AccessTarget>);
+
+/// A user or group name with the ids it resolved to, used to evaluate its effective access to
+/// an entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessTarget {
+    /// The name that was asked for.
+    pub name: String,
+    /// The resolved user id, if the name names a user.
+    #[cfg(unix)]
+    pub uid: Option<u32>,
+    /// The ids of the groups the user is a member of, or the group itself if the name names a
+    /// group.
+    #[cfg(unix)]
+    pub gids: Vec<u32>,
+}
+
+impl AccessTarget {
+    /// Resolve a user or group name into the ids used for the access evaluation.
+    #[cfg(unix)]
+    pub fn from_name(name: &str) -> Self {
+        use users::{get_group_by_name, get_user_by_name, get_user_groups};
+
+        let user = get_user_by_name(name);
+        let uid = user.as_ref().map(|user| user.uid());
+
+        let mut gids: Vec<u32> = match &user {
+            Some(user) => get_user_groups(name, user.primary_group_id())
+                .unwrap_or_default()
+                .iter()
+                .map(|group| group.gid())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        if let Some(group) = get_group_by_name(name) {
+            gids.push(group.gid());
+        }
+
+        Self {
+            name: name.to_string(),
+            uid,
+            gids,
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_name(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Configurable<Self> for CheckAccess {
+    /// Get a potential `CheckAccess` value from [ArgMatches].
+    ///
+    /// If the "check-access" argument is passed, this returns a `CheckAccess` with the resolved
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("check-access") > 0 {
+            matches
+                .value_of("check-access")
+                .map(|name| Self(Some(AccessTarget::from_name(name))))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `CheckAccess` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by
+    /// "check-access", this returns its resolved value in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["check-access"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => Some(Self(Some(AccessTarget::from_name(value)))),
+                _ => {
+                    config.print_wrong_type_warning("check-access", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CheckAccess;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, CheckAccess::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_name() {
+        let argv = vec!["lsd", "--check-access", "some-one"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = CheckAccess::from_arg_matches(&matches);
+        assert_eq!(
+            Some(String::from("some-one")),
+            result.and_then(|result| result.0).map(|target| target.name)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, CheckAccess::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, CheckAccess::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_name() {
+        let yaml_string = "check-access: some-one";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let result = CheckAccess::from_config(&Config::with_yaml(yaml));
+        assert_eq!(
+            Some(String::from("some-one")),
+            result.and_then(|result| result.0).map(|target| target.name)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! This module defines the [CollapseGlobs]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](CollapseGlobs::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use yaml_rust::Yaml;
+
+/// The struct holding the [GlobSet] of patterns whose matching runs of entries are
+/// collapsed into one summary row.
+#[derive(Clone, Debug)]
+pub struct CollapseGlobs(pub GlobSet);
+
+impl CollapseGlobs {
+    /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first value
+    /// that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](CollapseGlobs::from_arg_matches)
+    /// - [from_config](CollapseGlobs::from_config)
+    /// - [Default::default]
+    ///
+    /// # Note
+    ///
+    /// The configuration file's Yaml is read in any case, to be able to check for errors and print
+    /// out warnings.
+    ///
+    /// # Errors
+    ///
+    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                match value {
+                    Ok(glob_set) => result = Ok(Self(glob_set)),
+                    Err(err) => result = Err(err),
+                }
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            match value {
+                Ok(glob_set) => result = Ok(Self(glob_set)),
+                Err(err) => result = Err(err),
+            }
+        }
+
+        result
+    }
+
+    /// Get a potential [GlobSet] from [ArgMatches].
+    ///
+    /// If the "collapse" argument has been passed, this returns a [Result] in a [Some] with
+    /// either the built [GlobSet] or an [Error], if any error was encountered while creating the
+    /// [GlobSet]. If the argument has not been passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<GlobSet, Error>> {
+        if matches.occurrences_of("collapse") > 0 {
+            if let Some(values) = matches.values_of("collapse") {
+                let mut glob_set_builder = GlobSetBuilder::new();
+                for value in values {
+                    match Self::create_glob(value) {
+                        Ok(glob) => {
+                            glob_set_builder.add(glob);
+                        }
+                        Err(err) => return Some(Err(err)),
+                    }
+                }
+                Some(Self::create_glob_set(&glob_set_builder))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential [GlobSet] from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by "collapse-globs",
+    /// each of its [String](Yaml::String) values is used to build the [GlobSet]. If the building
+    /// succeeds, the [GlobSet] is returned in the [Result] in a [Some]. If any error is
+    /// encountered while building, an [Error] is returned in the Result instead. If the Yaml does
+    /// not contain such a key, this returns [None].
+    fn from_config(config: &Config) -> Option<Result<GlobSet, Error>> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["collapse-globs"] {
+                Yaml::BadValue => None,
+                Yaml::Array(values) => {
+                    let mut glob_set_builder = GlobSetBuilder::new();
+                    for yaml_str in values.iter() {
+                        if let Yaml::String(value) = yaml_str {
+                            match Self::create_glob(value) {
+                                Ok(glob) => {
+                                    glob_set_builder.add(glob);
+                                }
+                                Err(err) => return Some(Err(err)),
+                            }
+                        } else {
+                            config.print_wrong_type_warning("collapse-globs", "string");
+                        }
+                    }
+                    Some(Self::create_glob_set(&glob_set_builder))
+                }
+                _ => {
+                    config.print_wrong_type_warning("collapse-globs", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Create a [Glob] from a provided pattern.
+    ///
+    /// This method is mainly a helper to wrap the handling of potential errors.
+    fn create_glob(pattern: &str) -> Result<Glob, Error> {
+        match Glob::new(pattern) {
+            Ok(glob) => Ok(glob),
+            Err(err) => Err(Error::with_description(
+                &err.to_string(),
+                ErrorKind::ValueValidation,
+            )),
+        }
+    }
+
+    /// Create a [GlobSet] from a provided [GlobSetBuilder].
+    ///
+    /// This method is mainly a helper to wrap the handling of potential errors.
+    fn create_glob_set(builder: &GlobSetBuilder) -> Result<GlobSet, Error> {
+        match builder.build() {
+            Ok(glob_set) => Ok(glob_set),
+            Err(err) => Err(Error::with_description(
+                &err.to_string(),
+                ErrorKind::ValueValidation,
+            )),
+        }
+    }
+}
+
+/// The default value of `CollapseGlobs` is the empty [GlobSet], returned by [GlobSet::empty()].
+impl Default for CollapseGlobs {
+    fn default() -> Self {
+        Self(GlobSet::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CollapseGlobs;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    // The following tests are implemented using match expressions instead of the assert_eq macro,
+    // because clap::Error does not implement PartialEq.
+    //
+    // Further no tests for actually returned GlobSets are implemented, because GlobSet does not
+    // even implement PartialEq and thus can not be easily compared.
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match CollapseGlobs::from_arg_matches(&matches) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert!(match CollapseGlobs::from_config(&Config::with_none()) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert!(match CollapseGlobs::from_config(&Config::with_yaml(yaml)) {
+            None => true,
+            _ => false,
+        });
+    }
+}
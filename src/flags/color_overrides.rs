@@ -0,0 +1,134 @@
+//! This module defines the [ColorOverrides] flag. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag holding `LS_COLORS`-style fragments which are applied on top of the environment's
+/// color mapping.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ColorOverrides(pub Vec<String>);
+
+impl Configurable<Self> for ColorOverrides {
+    /// Get a potential `ColorOverrides` value from [ArgMatches].
+    ///
+    /// If the "color-override" argument is passed, this returns a `ColorOverrides` holding all
+    /// of its parameters in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("color-override") > 0 {
+            matches
+                .values_of("color-override")
+                .map(|values| Self(values.map(String::from).collect()))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ColorOverrides` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Array](Yaml::Array) of [String](Yaml::String) values
+    /// pointed to by "color-overrides", this returns a `ColorOverrides` holding them in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["color-overrides"] {
+                Yaml::BadValue => None,
+                Yaml::Array(values) => {
+                    let mut overrides = Vec::new();
+                    for yaml_str in values.iter() {
+                        match yaml_str {
+                            Yaml::String(value) => overrides.push(value.clone()),
+                            _ => {
+                                config.print_wrong_type_warning("color-overrides", "string");
+                                return None;
+                            }
+                        }
+                    }
+                    Some(Self(overrides))
+                }
+                _ => {
+                    config.print_wrong_type_warning("color-overrides", "array of strings");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColorOverrides;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorOverrides::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_one() {
+        let argv = vec!["lsd", "--color-override", "di=34;1"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorOverrides(vec![String::from("di=34;1")])),
+            ColorOverrides::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_multiple() {
+        let argv = vec![
+            "lsd",
+            "--color-override",
+            "di=34;1",
+            "--color-override",
+            "*.log=90",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorOverrides(vec![
+                String::from("di=34;1"),
+                String::from("*.log=90")
+            ])),
+            ColorOverrides::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorOverrides::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ColorOverrides::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_fragments() {
+        let yaml_string = "color-overrides:\n  - di=34;1\n  - '*.log=90'";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(ColorOverrides(vec![
+                String::from("di=34;1"),
+                String::from("*.log=90")
+            ])),
+            ColorOverrides::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! This module defines the [ColorSource] flag. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing where the entry colors are taken from.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum ColorSource {
+    /// The variant to honor the `LS_COLORS` environment variable on top of the theme, so
+    /// existing dircolors setups carry over.
+    LsColors,
+    /// The variant to use the theme alone, ignoring `LS_COLORS`.
+    Theme,
+}
+
+impl Configurable<Self> for ColorSource {
+    /// Get a potential `ColorSource` variant from [ArgMatches].
+    ///
+    /// If one of the source names is passed, the corresponding `ColorSource` variant is
+    /// returned in a [Some]. If none of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("color-source") > 0 {
+            match matches.value_of("color-source") {
+                Some("ls-colors") => Some(Self::LsColors),
+                Some("theme") => Some(Self::Theme),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ColorSource` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "color-source" and it names one of the sources, this returns the corresponding
+    /// `ColorSource` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["color-source"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "ls-colors" => Some(Self::LsColors),
+                    "theme" => Some(Self::Theme),
+                    _ => {
+                        config.print_invalid_value_warning("color-source", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("color-source", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `ColorSource` is [ColorSource::LsColors].
+impl Default for ColorSource {
+    fn default() -> Self {
+        Self::LsColors
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColorSource;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorSource::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_theme() {
+        let argv = vec!["lsd", "--color-source", "theme"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorSource::Theme),
+            ColorSource::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_ls_colors() {
+        let argv = vec!["lsd", "--color-source", "ls-colors"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorSource::LsColors),
+            ColorSource::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorSource::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_theme() {
+        let yaml_string = "color-source: theme";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(ColorSource::Theme),
+            ColorSource::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "color-source: dircolors";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ColorSource::from_config(&Config::with_yaml(yaml)));
+    }
+}
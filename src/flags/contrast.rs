@@ -0,0 +1,280 @@
+//! This module defines the [Contrast] options. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Contrast::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The options relating to the minimum contrast enforcement.
+#[derive(Clone, Debug, Copy, PartialEq, Default)]
+pub struct Contrast {
+    /// The minimum contrast ratio the theme colors have to reach against the background, if
+    /// any.
+    pub ratio: Option<f32>,
+    /// The background color to enforce the ratio against, if one was configured.
+    pub background: Option<(u8, u8, u8)>,
+}
+
+impl Contrast {
+    /// Get the Contrast from either [ArgMatches], a [Config] or the [Default] value.
+    ///
+    /// The ratio is determined by [ratio_from](Contrast::ratio_from) and the background by
+    /// [background_from_config](Contrast::background_from_config).
+    ///
+    /// # Errors
+    ///
+    /// If [ratio_from](Contrast::ratio_from) returns an [Error], this returns it.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let ratio = Self::ratio_from(matches, config)?;
+        let background = Self::background_from_config(config);
+        Ok(Self { ratio, background })
+    }
+
+    /// Get the ratio from [ArgMatches], a [Config] or the [Default] value. The first value
+    /// that is not [None] is used. The order of precedence for the value used is:
+    /// - [ratio_from_arg_matches](Contrast::ratio_from_arg_matches)
+    /// - [ratio_from_config](Contrast::ratio_from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If [ratio_from_arg_matches](Contrast::ratio_from_arg_matches) returns an [Error], this
+    /// returns it.
+    fn ratio_from(matches: &ArgMatches, config: &Config) -> Result<Option<f32>, Error> {
+        let mut result: Result<Option<f32>, Error> = Ok(None);
+
+        if config.has_yaml() {
+            if let Some(value) = Self::ratio_from_config(config) {
+                result = Ok(Some(value));
+            }
+        }
+
+        if let Some(value) = Self::ratio_from_arg_matches(matches) {
+            result = value.map(Some);
+        }
+
+        result
+    }
+
+    /// Get a potential ratio from [ArgMatches].
+    ///
+    /// If the "min-contrast" argument is passed, its parameter is evaluated. If it can be
+    /// parsed into a [f32] of at least 1, the [Result] is returned in the [Some]. If it can
+    /// not be parsed an [Error] is returned in the [Some]. If the argument has not been
+    /// passed, a [None] is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the parameter to the "min-contrast" argument can not be parsed, this returns an
+    /// [Error] in a [Some].
+    fn ratio_from_arg_matches(matches: &ArgMatches) -> Option<Result<f32, Error>> {
+        if let Some(str) = matches.value_of("min-contrast") {
+            match str.parse::<f32>() {
+                Ok(value) if (1.0..=21.0).contains(&value) => return Some(Ok(value)),
+                _ => {
+                    return Some(Err(Error::with_description(
+                        "The argument '--min-contrast' requires a ratio between 1 and 21.",
+                        ErrorKind::ValueValidation,
+                    )))
+                }
+            }
+        }
+        None
+    }
+
+    /// Get a potential ratio from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Real](Yaml::Real) or [Integer](Yaml::Integer) value
+    /// between 1 and 21 pointed to by "contrast" -> "ratio", this returns its value in a
+    /// [Some]. Otherwise this returns [None].
+    fn ratio_from_config(config: &Config) -> Option<f32> {
+        if let Some(yaml) = &config.yaml {
+            let value = match &yaml["contrast"]["ratio"] {
+                Yaml::BadValue => return None,
+                Yaml::Real(value) => value.parse::<f32>().ok(),
+                Yaml::Integer(value) => Some(*value as f32),
+                _ => {
+                    config.print_wrong_type_warning("contrast->ratio", "number");
+                    return None;
+                }
+            };
+
+            match value {
+                Some(value) if (1.0..=21.0).contains(&value) => Some(value),
+                _ => {
+                    config
+                        .print_warning("The contrast->ratio value has to be between 1 and 21.");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential background color from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value holding a six digit hex
+    /// color pointed to by "contrast" -> "background", this returns its channels in a [Some].
+    /// Otherwise this returns [None].
+    fn background_from_config(config: &Config) -> Option<(u8, u8, u8)> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["contrast"]["background"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => {
+                    let value = value.trim_start_matches('#');
+                    let channels = match (
+                        u8::from_str_radix(value.get(0..2).unwrap_or_default(), 16),
+                        u8::from_str_radix(value.get(2..4).unwrap_or_default(), 16),
+                        u8::from_str_radix(value.get(4..6).unwrap_or_default(), 16),
+                    ) {
+                        (Ok(red), Ok(green), Ok(blue)) if value.len() == 6 => {
+                            Some((red, green, blue))
+                        }
+                        _ => None,
+                    };
+
+                    if channels.is_none() {
+                        config.print_warning(
+                            "The contrast->background value has to be a six digit hex color.",
+                        );
+                    }
+                    channels
+                }
+                _ => {
+                    config.print_wrong_type_warning("contrast->background", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Contrast;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use clap::ErrorKind;
+    use yaml_rust::YamlLoader;
+
+    // The following ratio_from_arg_matches tests are implemented using match expressions
+    // instead of the assert_eq macro, because clap::Error does not implement PartialEq.
+
+    #[test]
+    fn test_ratio_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Contrast::ratio_from_arg_matches(&matches) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_ratio_from_arg_matches_ratio() {
+        let argv = vec!["lsd", "--min-contrast", "4.5"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Contrast::ratio_from_arg_matches(&matches) {
+            None => false,
+            Some(result) => {
+                match result {
+                    Ok(value) => (value - 4.5).abs() < f32::EPSILON,
+                    Err(_) => false,
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_ratio_from_arg_matches_out_of_range() {
+        let argv = vec!["lsd", "--min-contrast", "42"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Contrast::ratio_from_arg_matches(&matches) {
+            None => false,
+            Some(result) => {
+                match result {
+                    Ok(_) => false,
+                    Err(error) => error.kind == ErrorKind::ValueValidation,
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_ratio_from_arg_matches_non_number() {
+        let argv = vec!["lsd", "--min-contrast", "foo"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Contrast::ratio_from_arg_matches(&matches) {
+            None => false,
+            Some(result) => {
+                match result {
+                    Ok(_) => false,
+                    Err(error) => error.kind == ErrorKind::ValueValidation,
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_ratio_from_config_none() {
+        assert_eq!(None, Contrast::ratio_from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_ratio_from_config_real() {
+        let yaml_string = "contrast:\n  ratio: 4.5";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(4.5),
+            Contrast::ratio_from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_ratio_from_config_integer() {
+        let yaml_string = "contrast:\n  ratio: 7";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(7.0),
+            Contrast::ratio_from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_ratio_from_config_out_of_range() {
+        let yaml_string = "contrast:\n  ratio: 42";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Contrast::ratio_from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_background_from_config_none() {
+        assert_eq!(None, Contrast::background_from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_background_from_config_hex() {
+        let yaml_string = "contrast:\n  background: '#ffffff'";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some((255, 255, 255)),
+            Contrast::background_from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_background_from_config_invalid() {
+        let yaml_string = "contrast:\n  background: white";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            None,
+            Contrast::background_from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -14,6 +14,7 @@ use yaml_rust::Yaml;
 pub enum DateFlag {
     Date,
     Relative,
+    Combined,
     Formatted(String),
 }
 
@@ -36,6 +37,7 @@ impl DateFlag {
         match value {
             "date" => Some(Self::Date),
             "relative" => Some(Self::Relative),
+            "combined" => Some(Self::Combined),
             _ if value.starts_with('+') => Self::from_format_string(&value, &config),
             _ => {
                 config.print_warning(&format!("Not a valid date value: {}", value));
@@ -58,6 +60,7 @@ impl Configurable<Self> for DateFlag {
             match matches.value_of("date") {
                 Some("date") => Some(Self::Date),
                 Some("relative") => Some(Self::Relative),
+                Some("combined") => Some(Self::Combined),
                 Some(format) if format.starts_with('+') => {
                     Some(Self::Formatted(format[1..].to_owned()))
                 }
@@ -73,7 +76,7 @@ impl Configurable<Self> for DateFlag {
     /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by "classic"
     /// and its value is `true`, then this returns the [DateFlag::Date] variant in a [Some].
     /// Otherwise if the Yaml contains a [String](Yaml::String) value pointed to by "date" and it
-    /// is one of "date" or "relative", this returns its corresponding variant in a [Some].
+    /// is one of "date", "relative" or "combined", this returns its corresponding variant in a [Some].
     /// Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
@@ -119,6 +122,26 @@ mod test {
         assert_eq!(None, DateFlag::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_combined() {
+        let argv = vec!["lsd", "--date", "combined"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Combined),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_combined() {
+        let yaml_string = "date: combined";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateFlag::Combined),
+            DateFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
     #[test]
     fn test_from_arg_matches_date() {
         let argv = vec!["lsd", "--date", "date"];
@@ -146,6 +169,16 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_format_with_literals() {
+        let argv = vec!["lsd", "--date", "+%Y-%m-%d %H:%M"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     #[should_panic(expected = "invalid format specifier: %J")]
     fn test_from_arg_matches_format_invalid() {
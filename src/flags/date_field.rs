@@ -0,0 +1,173 @@
+//! This module defines the [DateField]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which timestamp the date column and the time sort use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum DateField {
+    /// The variant to use the modification time.
+    Mod,
+    /// The variant to use the access time, like `ls -u`.
+    Access,
+    /// The variant to use the status change time, like `ls -c`.
+    Change,
+    /// The variant to use the creation time, where the filesystem records one.
+    Birth,
+}
+
+impl Configurable<Self> for DateField {
+    /// Get a potential `DateField` variant from [ArgMatches].
+    ///
+    /// If any of the "mod", "access", "change" or "birth" arguments is passed, the corresponding
+    /// `DateField` variant is returned in a [Some]. If neither of them is passed, this returns
+    /// [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("date-field") > 0 {
+            match matches.value_of("date-field") {
+                Some("mod") => Some(Self::Mod),
+                Some("access") => Some(Self::Access),
+                Some("change") => Some(Self::Change),
+                Some("birth") => Some(Self::Birth),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DateField` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "date-field" and it is one of "mod", "access", "change" or "birth", this returns the
+    /// corresponding `DateField` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["date-field"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "mod" => Some(Self::Mod),
+                    "access" => Some(Self::Access),
+                    "change" => Some(Self::Change),
+                    "birth" => Some(Self::Birth),
+                    _ => {
+                        config.print_invalid_value_warning("date-field", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("date-field", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `DateField` is [DateField::Mod].
+impl Default for DateField {
+    fn default() -> Self {
+        Self::Mod
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DateField;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DateField::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_mod() {
+        let argv = vec!["lsd", "--date-field", "mod"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DateField::Mod), DateField::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_access() {
+        let argv = vec!["lsd", "--date-field", "access"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateField::Access),
+            DateField::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_change() {
+        let argv = vec!["lsd", "--date-field", "change"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateField::Change),
+            DateField::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_birth() {
+        let argv = vec!["lsd", "--date-field", "birth"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateField::Birth),
+            DateField::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DateField::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DateField::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_access() {
+        let yaml_string = "date-field: access";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateField::Access),
+            DateField::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_birth() {
+        let yaml_string = "date-field: birth";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateField::Birth),
+            DateField::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "date-field: yesterday";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DateField::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,138 @@
+//! This module defines the [DateGranularity]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing how detailed the relative date style is.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum DateGranularity {
+    /// The variant to round to the largest unit, like "2 days ago".
+    Rough,
+    /// The variant to spell out every component, like "2 days, 3 hours and 10 minutes ago".
+    Precise,
+}
+
+impl Configurable<Self> for DateGranularity {
+    /// Get a potential `DateGranularity` variant from [ArgMatches].
+    ///
+    /// If either the "rough" or "precise" argument is passed, the corresponding
+    /// `DateGranularity` variant is returned in a [Some]. If neither of them is passed, this
+    /// returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("date-granularity") > 0 {
+            match matches.value_of("date-granularity") {
+                Some("rough") => Some(Self::Rough),
+                Some("precise") => Some(Self::Precise),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DateGranularity` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "date-granularity" and it is either "rough" or "precise", this returns the corresponding
+    /// `DateGranularity` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["date-granularity"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "rough" => Some(Self::Rough),
+                    "precise" => Some(Self::Precise),
+                    _ => {
+                        config.print_invalid_value_warning("date-granularity", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("date-granularity", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `DateGranularity` is [DateGranularity::Rough].
+impl Default for DateGranularity {
+    fn default() -> Self {
+        Self::Rough
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DateGranularity;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DateGranularity::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_rough() {
+        let argv = vec!["lsd", "--date-granularity", "rough"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateGranularity::Rough),
+            DateGranularity::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_precise() {
+        let argv = vec!["lsd", "--date-granularity", "precise"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateGranularity::Precise),
+            DateGranularity::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DateGranularity::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DateGranularity::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_precise() {
+        let yaml_string = "date-granularity: precise";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateGranularity::Precise),
+            DateGranularity::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "date-granularity: exact";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DateGranularity::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,177 @@
+//! This module defines the [ExpectMode] option. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use the [configure_from](ExpectMode::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The expected permission modes of files and directories, against which the listed entries
+/// are checked. [None] disables the check.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct ExpectMode(pub Option<(u32, u32)>);
+
+impl ExpectMode {
+    /// Get the ExpectMode from either [ArgMatches], a [Config] or the [Default] value. The
+    /// first value that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](ExpectMode::from_arg_matches)
+    /// - [from_config](ExpectMode::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If the parameter to the "expect-mode" argument can not be parsed, this returns an
+    /// [Error].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = Ok(value);
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `ExpectMode` from [ArgMatches].
+    ///
+    /// If the "expect-mode" argument is passed, its parameter is evaluated. If it can be
+    /// parsed into a pair of octal modes, the [Result] is returned in the [Some]. If it can
+    /// not be parsed an [Error] is returned in the [Some]. If the argument has not been
+    /// passed, a [None] is returned.
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        matches.value_of("expect-mode").map(|value| {
+            match parse_modes(value) {
+                Some(modes) => Ok(Self(Some(modes))),
+                None => Err(Error::with_description(
+                    "The argument '--expect-mode' requires two octal modes separated by a \
+                     colon, like '644:755'.",
+                    ErrorKind::ValueValidation,
+                )),
+            }
+        })
+    }
+
+    /// Get a potential `ExpectMode` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by
+    /// "expect-mode" and it holds two octal modes separated by a colon, this returns the pair
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["expect-mode"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match parse_modes(value) {
+                    Some(modes) => Some(Self(Some(modes))),
+                    None => {
+                        config.print_invalid_value_warning("expect-mode", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("expect-mode", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a `files:dirs` pair of octal modes, like `644:755`.
+fn parse_modes(value: &str) -> Option<(u32, u32)> {
+    let (files, dirs) = value.split_once(':')?;
+    Some((parse_mode(files)?, parse_mode(dirs)?))
+}
+
+/// Parse one octal mode of up to four digits, the optional leading one holding the setuid,
+/// setgid and sticky bits.
+fn parse_mode(value: &str) -> Option<u32> {
+    if value.is_empty() || value.len() > 4 {
+        return None;
+    }
+
+    u32::from_str_radix(value, 8).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExpectMode;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use clap::ErrorKind;
+    use yaml_rust::YamlLoader;
+
+    // The from_arg_matches tests are implemented using match expressions instead of the
+    // assert_eq macro, because clap::Error does not implement PartialEq.
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match ExpectMode::from_arg_matches(&matches) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_pair() {
+        let argv = vec!["lsd", "--expect-mode", "644:755"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match ExpectMode::from_arg_matches(&matches) {
+            Some(Ok(ExpectMode(Some((0o644, 0o755))))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_missing_colon() {
+        let argv = vec!["lsd", "--expect-mode", "644"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match ExpectMode::from_arg_matches(&matches) {
+            Some(Err(error)) => error.kind == ErrorKind::ValueValidation,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_non_octal() {
+        let argv = vec!["lsd", "--expect-mode", "648:755"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match ExpectMode::from_arg_matches(&matches) {
+            Some(Err(error)) => error.kind == ErrorKind::ValueValidation,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ExpectMode::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_pair() {
+        let yaml_string = "expect-mode: \"600:700\"";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(ExpectMode(Some((0o600, 0o700)))),
+            ExpectMode::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "expect-mode: everything";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ExpectMode::from_config(&Config::with_yaml(yaml)));
+    }
+}
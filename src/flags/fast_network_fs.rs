@@ -0,0 +1,104 @@
+//! This module defines the [FastNetworkFs] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing whether to skip expensive metadata probes on network filesystems.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FastNetworkFs(pub bool);
+
+impl Configurable<Self> for FastNetworkFs {
+    /// Get a potential `FastNetworkFs` value from [ArgMatches].
+    ///
+    /// If the "fast-network-fs" argument is passed, this returns a `FastNetworkFs` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("fast-network-fs") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `FastNetworkFs` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "fast-network-fs", this returns its value as the value of the `FastNetworkFs`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["fast-network-fs"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("fast-network-fs", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FastNetworkFs;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, FastNetworkFs::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--fast-network-fs"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(FastNetworkFs(true)), FastNetworkFs::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FastNetworkFs::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, FastNetworkFs::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "fast-network-fs: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(FastNetworkFs(true)),
+            FastNetworkFs::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "fast-network-fs: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(FastNetworkFs(false)),
+            FastNetworkFs::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! This module defines the [FollowLinksAtDepth] option. To set it up from [ArgMatches], a
+//! [Yaml] and its [Default] value, use the
+//! [configure_from](FollowLinksAtDepth::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The depth down to which the tree layout expands symlinked directories. [None] keeps the
+/// default of expanding them like plain directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct FollowLinksAtDepth(pub Option<usize>);
+
+impl FollowLinksAtDepth {
+    /// Get the FollowLinksAtDepth from either [ArgMatches], a [Config] or the [Default] value.
+    /// The first value that is not [None] is used. The order of precedence for the value used
+    /// is:
+    /// - [from_arg_matches](FollowLinksAtDepth::from_arg_matches)
+    /// - [from_config](FollowLinksAtDepth::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If the parameter to the "follow-links-at-depth" argument can not be parsed, this returns
+    /// an [Error].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = Ok(value);
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `FollowLinksAtDepth` from [ArgMatches].
+    ///
+    /// If the "follow-links-at-depth" argument is passed, its parameter is evaluated. If it can
+    /// be parsed into a [usize], the [Result] is returned in the [Some]. If it can not be
+    /// parsed an [Error] is returned in the [Some]. If the argument has not been passed, a
+    /// [None] is returned.
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if let Some(str) = matches.value_of("follow-links-at-depth") {
+            match str.parse::<usize>() {
+                Ok(value) => return Some(Ok(Self(Some(value)))),
+                Err(_) => {
+                    return Some(Err(Error::with_description(
+                        "The argument '--follow-links-at-depth' requires a valid positive number.",
+                        ErrorKind::ValueValidation,
+                    )))
+                }
+            }
+        }
+        None
+    }
+
+    /// Get a potential `FollowLinksAtDepth` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a non-negative [Integer](Yaml::Integer) value pointed to
+    /// by "follow-links-at-depth", this returns its value in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["follow-links-at-depth"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) => {
+                    if *value >= 0 {
+                        Some(Self(Some(*value as usize)))
+                    } else {
+                        config.print_warning(
+                            "The follow-links-at-depth value can not be negative.",
+                        );
+                        None
+                    }
+                }
+                _ => {
+                    config.print_wrong_type_warning("follow-links-at-depth", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FollowLinksAtDepth;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use clap::ErrorKind;
+    use yaml_rust::YamlLoader;
+
+    // The from_arg_matches tests are implemented using match expressions instead of the
+    // assert_eq macro, because clap::Error does not implement PartialEq.
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match FollowLinksAtDepth::from_arg_matches(&matches) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_integer() {
+        let argv = vec!["lsd", "--follow-links-at-depth", "2"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match FollowLinksAtDepth::from_arg_matches(&matches) {
+            Some(Ok(FollowLinksAtDepth(Some(2)))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_non_int() {
+        let argv = vec!["lsd", "--follow-links-at-depth", "foo"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match FollowLinksAtDepth::from_arg_matches(&matches) {
+            Some(Err(error)) => error.kind == ErrorKind::ValueValidation,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FollowLinksAtDepth::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_integer() {
+        let yaml_string = "follow-links-at-depth: 1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(FollowLinksAtDepth(Some(1))),
+            FollowLinksAtDepth::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_neg_integer() {
+        let yaml_string = "follow-links-at-depth: -1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            None,
+            FollowLinksAtDepth::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
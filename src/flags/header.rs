@@ -0,0 +1,236 @@
+//! This module defines the [Header] flag and the [HeaderCaptions] holding the texts of the
+//! caption row. To set them up from [ArgMatches], a [Yaml] and their [Default] values, use
+//! the [configure_from](Configurable::configure_from) method.
+
+use super::blocks::Block;
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use yaml_rust::Yaml;
+
+/// The flag showing whether to print a caption row above the blocks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Header(pub bool);
+
+impl Configurable<Self> for Header {
+    /// Get a potential `Header` value from [ArgMatches].
+    ///
+    /// If the "header" argument is passed, this returns a `Header` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("header") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Header` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "header", this returns its value as the value of the `Header`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["header"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("header", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Header;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Header::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--header"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Header(true)), Header::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Header::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Header::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "header: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Header(true)),
+            Header::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "header: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Header(false)),
+            Header::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
+
+/// The captions of the header row, keyed by [Block]. Captions not overridden through the
+/// configuration fall back to a default localized through `LC_MESSAGES`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct HeaderCaptions(pub HashMap<Block, String>);
+
+impl HeaderCaptions {
+    /// The caption for the given [Block]: the configured one if present, the localized
+    /// default otherwise.
+    pub fn caption(&self, block: &Block) -> String {
+        if let Some(caption) = self.0.get(block) {
+            return caption.clone();
+        }
+
+        localized_caption(block).to_string()
+    }
+}
+
+impl Configurable<Self> for HeaderCaptions {
+    /// The captions can only be renamed through the configuration file.
+    fn from_arg_matches(_matches: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `HeaderCaptions` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Hash](Yaml::Hash) value pointed to by
+    /// "header-captions", its [String](Yaml::String) values are returned keyed by their
+    /// blocks in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["header-captions"] {
+                Yaml::BadValue => None,
+                Yaml::Hash(hash) => {
+                    let mut captions = HashMap::new();
+                    for (key, value) in hash {
+                        match (key, value) {
+                            (Yaml::String(key), Yaml::String(value)) => {
+                                match Block::try_from(key.as_ref()) {
+                                    Ok(block) => {
+                                        captions.insert(block, value.clone());
+                                    }
+                                    Err(_) => config
+                                        .print_invalid_value_warning("header-captions", key),
+                                }
+                            }
+                            _ => config.print_wrong_type_warning("header-captions", "string"),
+                        }
+                    }
+                    Some(Self(captions))
+                }
+                _ => {
+                    config.print_wrong_type_warning("header-captions", "hash");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default caption of the given [Block] in the language named by the locale environment
+/// variables, falling back to English.
+fn localized_caption(block: &Block) -> &'static str {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+
+    match locale.split(&['_', '.'][..]).next().unwrap_or("") {
+        "de" => match block {
+            Block::Permission => "Rechte",
+            Block::User => "Benutzer",
+            Block::Group => "Gruppe",
+            Block::Size | Block::SizeValue => "Gr\u{f6}\u{df}e",
+            Block::Date => "Datum",
+            Block::BirthDate => "Erstellt",
+            Block::AccessedAge => "Zugriff",
+            Block::Name => "Name",
+            _ => english_caption(block),
+        },
+        "fr" => match block {
+            Block::Permission => "Droits",
+            Block::User => "Utilisateur",
+            Block::Group => "Groupe",
+            Block::Size | Block::SizeValue => "Taille",
+            Block::Date => "Date",
+            Block::BirthDate => "Cr\u{e9}\u{e9}",
+            Block::AccessedAge => "Acc\u{e8}s",
+            Block::Name => "Nom",
+            _ => english_caption(block),
+        },
+        "es" => match block {
+            Block::Permission => "Permisos",
+            Block::User => "Usuario",
+            Block::Group => "Grupo",
+            Block::Size | Block::SizeValue => "Tama\u{f1}o",
+            Block::Date => "Fecha",
+            Block::BirthDate => "Creado",
+            Block::AccessedAge => "Acceso",
+            Block::Name => "Nombre",
+            _ => english_caption(block),
+        },
+        _ => english_caption(block),
+    }
+}
+
+/// The default English caption of the given [Block].
+fn english_caption(block: &Block) -> &'static str {
+    match block {
+        Block::Permission => "Permissions",
+        Block::User => "User",
+        Block::Group => "Group",
+        Block::Size | Block::SizeValue => "Size",
+        Block::Date => "Date",
+        Block::BirthDate => "Created",
+        Block::AccessedAge => "Accessed",
+        Block::Name => "Name",
+        Block::INode => "Inode",
+        Block::Links => "Links",
+        Block::Context => "Context",
+        Block::Access => "Access",
+        Block::GitStatus => "Git",
+        Block::TypeIcon => "Icon",
+    }
+}
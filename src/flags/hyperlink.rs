@@ -0,0 +1,121 @@
+//! This module defines the [HyperlinkOption]. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing when to wrap the file names in OSC 8 hyperlinks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum HyperlinkOption {
+    Always,
+    Auto,
+    Never,
+}
+
+impl Configurable<Self> for HyperlinkOption {
+    /// Get a potential `HyperlinkOption` variant from [ArgMatches].
+    ///
+    /// If the "hyperlink" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("hyperlink") > 0 {
+            match matches.value_of("hyperlink") {
+                Some("always") => Some(Self::Always),
+                Some("auto") => Some(Self::Auto),
+                Some("never") => Some(Self::Never),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `HyperlinkOption` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by
+    /// "hyperlink" and it names one of the variants, this returns the corresponding
+    /// `HyperlinkOption` in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["hyperlink"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "always" => Some(Self::Always),
+                    "auto" => Some(Self::Auto),
+                    "never" => Some(Self::Never),
+                    _ => {
+                        config.print_invalid_value_warning("hyperlink", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("hyperlink", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `HyperlinkOption` is [HyperlinkOption::Never].
+impl Default for HyperlinkOption {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HyperlinkOption;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, HyperlinkOption::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_always() {
+        let argv = vec!["lsd", "--hyperlink", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(HyperlinkOption::Always),
+            HyperlinkOption::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, HyperlinkOption::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_auto() {
+        let yaml_string = "hyperlink: auto";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(HyperlinkOption::Auto),
+            HyperlinkOption::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "hyperlink: sometimes";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, HyperlinkOption::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -6,15 +6,18 @@ use super::Configurable;
 use crate::config_file::Config;
 
 use clap::ArgMatches;
+use std::collections::HashMap;
 use yaml_rust::Yaml;
 
 /// A collection of flags on how to use icons.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Icons {
     /// When to use icons.
     pub when: IconOption,
     /// Which icon theme to use.
     pub theme: IconTheme,
+    /// The user icon mappings layered on top of the theme.
+    pub overrides: IconOverrides,
 }
 
 impl Icons {
@@ -25,7 +28,79 @@ impl Icons {
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = IconOption::configure_from(matches, config);
         let theme = IconTheme::configure_from(matches, config);
-        Self { when, theme }
+        let overrides = IconOverrides::configure_from(config);
+        Self {
+            when,
+            theme,
+            overrides,
+        }
+    }
+}
+
+/// The icon mappings from the `icons->overrides` section of the configuration file, so exact
+/// names and extensions can get specific glyphs without recompiling the built-in tables.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconOverrides {
+    /// Icons for exact file or directory names.
+    pub name: HashMap<String, String>,
+    /// Icons for file extensions.
+    pub extension: HashMap<String, String>,
+}
+
+impl IconOverrides {
+    /// Get the `IconOverrides` from a [Config], falling back to the empty [Default] value.
+    /// There is no command line equivalent, since the mappings are too unwieldy for it.
+    fn configure_from(config: &Config) -> Self {
+        Self::from_config(config).unwrap_or_default()
+    }
+
+    /// Get a potential `IconOverrides` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Hash](Yaml::Hash) value pointed to by "icons" ->
+    /// "overrides", its "names" and "extensions" mappings are returned in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let yaml = config.yaml.as_ref()?;
+
+        let overrides = &yaml["icons"]["overrides"];
+        match overrides {
+            Yaml::BadValue => None,
+            Yaml::Hash(_) => Some(Self {
+                name: Self::collect(config, &overrides["names"], "icons->overrides->names"),
+                extension: Self::collect(
+                    config,
+                    &overrides["extensions"],
+                    "icons->overrides->extensions",
+                ),
+            }),
+            _ => {
+                config.print_wrong_type_warning("icons->overrides", "hash");
+                None
+            }
+        }
+    }
+
+    /// Collect one mapping of [String](Yaml::String) keys to [String](Yaml::String) glyphs.
+    fn collect(config: &Config, yaml: &Yaml, key: &str) -> HashMap<String, String> {
+        match yaml {
+            Yaml::BadValue => HashMap::new(),
+            Yaml::Hash(hash) => {
+                let mut icons = HashMap::new();
+                for (name, icon) in hash {
+                    match (name, icon) {
+                        (Yaml::String(name), Yaml::String(icon)) => {
+                            icons.insert(name.clone(), icon.clone());
+                        }
+                        _ => config.print_wrong_type_warning(key, "string"),
+                    }
+                }
+                icons
+            }
+            _ => {
+                config.print_wrong_type_warning(key, "hash");
+                HashMap::new()
+            }
+        }
     }
 }
 
@@ -113,6 +188,7 @@ impl Default for IconOption {
 pub enum IconTheme {
     Unicode,
     Fancy,
+    Emoji,
 }
 
 impl IconTheme {
@@ -122,6 +198,7 @@ impl IconTheme {
         match value {
             "fancy" => Some(Self::Fancy),
             "unicode" => Some(Self::Unicode),
+            "emoji" => Some(Self::Emoji),
             _ => {
                 config.print_invalid_value_warning("icons->theme", &value);
                 None
@@ -140,6 +217,7 @@ impl Configurable<Self> for IconTheme {
             match matches.value_of("icon-theme") {
                 Some("fancy") => Some(Self::Fancy),
                 Some("unicode") => Some(Self::Unicode),
+                Some("emoji") => Some(Self::Emoji),
                 _ => panic!("This should not be reachable!"),
             }
         } else {
@@ -150,7 +228,7 @@ impl Configurable<Self> for IconTheme {
     /// Get a potential `IconTheme` variant from a [Config].
     ///
     /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "icons" ->
-    /// "theme" and it is one of "fancy" or "unicode", this returns its corresponding variant in a
+    /// "theme" and it is one of "fancy", "unicode" or "emoji", this returns its corresponding variant in a
     /// [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
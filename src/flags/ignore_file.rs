@@ -0,0 +1,118 @@
+//! This module defines the [IgnoreFile]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](IgnoreFile::configure_from) method.
+
+use crate::config_file::Config;
+use crate::print_error;
+
+use clap::{ArgMatches, Error};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use yaml_rust::Yaml;
+
+/// The struct holding a [GlobSet] built from the patterns of a user-specified ignore file.
+/// Entries whose names match are skipped during traversal.
+#[derive(Clone, Debug)]
+pub struct IgnoreFile(pub GlobSet);
+
+impl IgnoreFile {
+    /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first
+    /// value that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](IgnoreFile::from_arg_matches)
+    /// - [from_config](IgnoreFile::from_config)
+    /// - [Default::default]
+    ///
+    /// # Note
+    ///
+    /// The configuration file's Yaml is read in any case, to be able to check for errors and
+    /// print out warnings.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        if matches.occurrences_of("ignore-file") > 0 {
+            if let Some(path) = matches.value_of("ignore-file") {
+                return Ok(Self(from_patterns_file(path)));
+            }
+        }
+
+        if let Some(value) = Self::from_config(config) {
+            return Ok(value);
+        }
+
+        Ok(Default::default())
+    }
+
+    /// Get a potential [IgnoreFile] from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by
+    /// "ignore-file", this returns an `IgnoreFile` built from the file it points to in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["ignore-file"] {
+                Yaml::BadValue => None,
+                Yaml::String(path) => Some(Self(from_patterns_file(path))),
+                _ => {
+                    config.print_wrong_type_warning("ignore-file", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value returns an empty [GlobSet], which matches nothing.
+impl Default for IgnoreFile {
+    fn default() -> Self {
+        Self(GlobSet::empty())
+    }
+}
+
+/// Build a [GlobSet] from the patterns in the file at the given path, one pattern per line.
+/// Comments, blank lines and the negations of the gitignore format are skipped, and invalid
+/// patterns are reported without failing the listing.
+pub fn from_patterns_file(path: &str) -> GlobSet {
+    match std::fs::read_to_string(path) {
+        Ok(content) => from_patterns(&content),
+        Err(err) => {
+            print_error!("lsd: {}: {}\n", path, err);
+            GlobSet::empty()
+        }
+    }
+}
+
+/// Build a [GlobSet] from newline separated patterns, as found in ignore files.
+pub fn from_patterns(content: &str) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+
+        // Directory patterns like `target/` match the entry name itself.
+        let pattern = line.trim_start_matches('/').trim_end_matches('/');
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(err) => {
+                print_error!("lsd: invalid ignore pattern {}: {}\n", line, err);
+            }
+        }
+    }
+
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::from_patterns;
+
+    #[test]
+    fn test_from_patterns_matches_names() {
+        let globs = from_patterns("target/\n*.log\n# comment\n\n!kept.log\n");
+
+        assert!(globs.is_match("target"));
+        assert!(globs.is_match("build.log"));
+        assert!(!globs.is_match("main.rs"));
+    }
+}
@@ -95,6 +95,8 @@ impl IgnoreGlobs {
                                 }
                                 Err(err) => return Some(Err(err)),
                             }
+                        } else {
+                            config.print_wrong_type_warning("ignore-globs", "string");
                         }
                     }
                     Some(Self::create_glob_set(&glob_set_builder))
@@ -0,0 +1,155 @@
+//! This module defines the [LinkChain] option. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use the [configure_from](LinkChain::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The number of hops up to which nested symlinks are expanded into their full resolution
+/// chain. [None] keeps the default of showing only the immediate target.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct LinkChain(pub Option<usize>);
+
+impl LinkChain {
+    /// Get the LinkChain from either [ArgMatches], a [Config] or the [Default] value. The
+    /// first value that is not [None] is used. The order of precedence for the value used
+    /// is:
+    /// - [from_arg_matches](LinkChain::from_arg_matches)
+    /// - [from_config](LinkChain::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If the parameter to the "link-chain" argument can not be parsed, this returns an
+    /// [Error].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = Ok(value);
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `LinkChain` from [ArgMatches].
+    ///
+    /// If the "link-chain" argument is passed, its parameter is evaluated. If it can be
+    /// parsed into a [usize], the [Result] is returned in the [Some]. If it can not be
+    /// parsed an [Error] is returned in the [Some]. If the argument has not been passed, a
+    /// [None] is returned.
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if let Some(str) = matches.value_of("link-chain") {
+            match str.parse::<usize>() {
+                Ok(value) => return Some(Ok(Self(Some(value)))),
+                Err(_) => {
+                    return Some(Err(Error::with_description(
+                        "The argument '--link-chain' requires a valid positive number.",
+                        ErrorKind::ValueValidation,
+                    )))
+                }
+            }
+        }
+        None
+    }
+
+    /// Get a potential `LinkChain` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a non-negative [Integer](Yaml::Integer) value pointed
+    /// to by "link-chain", this returns its value in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["link-chain"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) => {
+                    if *value >= 0 {
+                        Some(Self(Some(*value as usize)))
+                    } else {
+                        config.print_warning("The link-chain value can not be negative.");
+                        None
+                    }
+                }
+                _ => {
+                    config.print_wrong_type_warning("link-chain", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LinkChain;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use clap::ErrorKind;
+    use yaml_rust::YamlLoader;
+
+    // The from_arg_matches tests are implemented using match expressions instead of the
+    // assert_eq macro, because clap::Error does not implement PartialEq.
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        match LinkChain::from_arg_matches(&matches) {
+            None => {}
+            _ => panic!("Passing no argument should yield None."),
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_depth() {
+        let argv = vec!["lsd", "--link-chain", "4"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        match LinkChain::from_arg_matches(&matches) {
+            Some(Ok(LinkChain(Some(4)))) => {}
+            _ => panic!("Passing a valid depth should yield its value."),
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--link-chain", "deep"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        match LinkChain::from_arg_matches(&matches) {
+            Some(Err(error)) => assert_eq!(error.kind, ErrorKind::ValueValidation),
+            _ => panic!("Passing an invalid depth should yield an error."),
+        }
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, LinkChain::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_depth() {
+        let yaml_string = "link-chain: 3";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(LinkChain(Some(3))),
+            LinkChain::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_negative() {
+        let yaml_string = "link-chain: -1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, LinkChain::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,191 @@
+//! This module defines the [MaxWidths] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](MaxWidths::configure_from) method.
+
+use super::blocks::Block;
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// The flag holding the maximum width per block, above which the block content is truncated.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct MaxWidths(pub HashMap<Block, usize>);
+
+impl MaxWidths {
+    /// Get the MaxWidths from either [ArgMatches], a [Config] or the [Default] value. The
+    /// caps from the configuration file and the command line are merged, with the command line
+    /// taking precedence per block.
+    ///
+    /// # Errors
+    ///
+    /// If [from_arg_matches](MaxWidths::from_arg_matches) returns an [Error], this returns it.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Self = Default::default();
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = value;
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result.0.extend(value?.0);
+        }
+
+        Ok(result)
+    }
+
+    /// Get a potential `MaxWidths` value from [ArgMatches].
+    ///
+    /// If the "max-width" argument has been passed, each of its parameters is parsed as a
+    /// "block=width" pair and the resulting `MaxWidths` is returned in a [Result] in a [Some].
+    /// If a parameter can not be parsed, an [Error] is returned in the Result instead. If the
+    /// argument has not been passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if matches.occurrences_of("max-width") > 0 {
+            let values = matches.values_of("max-width")?;
+
+            let mut widths = HashMap::new();
+            for value in values {
+                match Self::parse_cap(value) {
+                    Some((block, width)) => {
+                        widths.insert(block, width);
+                    }
+                    None => {
+                        return Some(Err(Error::with_description(
+                            "The argument '--max-width' requires a 'block=width' pair with a \
+                             valid block name and a positive number.",
+                            ErrorKind::ValueValidation,
+                        )))
+                    }
+                }
+            }
+            Some(Ok(Self(widths)))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `MaxWidths` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Hash](Yaml::Hash) pointed to by "max-widths", each of
+    /// its entries with a valid block name as the key and a positive [Integer](Yaml::Integer)
+    /// as the value is collected into the returned `MaxWidths` in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["max-widths"] {
+                Yaml::BadValue => None,
+                Yaml::Hash(hash) => {
+                    let mut widths = HashMap::new();
+                    for (key, value) in hash.iter() {
+                        match (key, value) {
+                            (Yaml::String(name), Yaml::Integer(width))
+                                if Block::try_from(name.as_str()).is_ok() && *width > 0 =>
+                            {
+                                widths
+                                    .insert(Block::try_from(name.as_str()).unwrap(), *width as usize);
+                            }
+                            _ => {
+                                config.print_warning(
+                                    "The max-widths entries have to map block names to \
+                                     positive numbers.",
+                                );
+                                return None;
+                            }
+                        }
+                    }
+                    Some(Self(widths))
+                }
+                _ => {
+                    config.print_wrong_type_warning("max-widths", "hash");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Parse a single "block=width" pair.
+    fn parse_cap(value: &str) -> Option<(Block, usize)> {
+        let mut parts = value.splitn(2, '=');
+        let block = Block::try_from(parts.next()?).ok()?;
+        let width = parts.next()?.parse::<usize>().ok().filter(|width| *width > 0)?;
+        Some((block, width))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MaxWidths;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Block;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(MaxWidths::from_arg_matches(&matches).is_none());
+    }
+
+    #[test]
+    fn test_from_arg_matches_pair() {
+        let argv = vec!["lsd", "--max-width", "name=20"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = MaxWidths::from_arg_matches(&matches).unwrap().unwrap();
+        assert_eq!(Some(&20), result.0.get(&Block::Name));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multiple_pairs() {
+        let argv = vec!["lsd", "--max-width", "user=8", "--max-width", "group=8"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = MaxWidths::from_arg_matches(&matches).unwrap().unwrap();
+        assert_eq!(Some(&8), result.0.get(&Block::User));
+        assert_eq!(Some(&8), result.0.get(&Block::Group));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_block() {
+        let argv = vec!["lsd", "--max-width", "foo=8"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(MaxWidths::from_arg_matches(&matches).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_width() {
+        let argv = vec!["lsd", "--max-width", "name=0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(MaxWidths::from_arg_matches(&matches).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, MaxWidths::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_hash() {
+        let yaml_string = "max-widths:\n  name: 20\n  group: 8";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let result = MaxWidths::from_config(&Config::with_yaml(yaml)).unwrap();
+        assert_eq!(Some(&20), result.0.get(&Block::Name));
+        assert_eq!(Some(&8), result.0.get(&Block::Group));
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "max-widths:\n  name: foo";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, MaxWidths::from_config(&Config::with_yaml(yaml)));
+    }
+}
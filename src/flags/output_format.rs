@@ -0,0 +1,120 @@
+//! This module defines the [OutputFormat] flag. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which output format to use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The variant to use the usual visual layouts.
+    Default,
+    /// The variant to emit the listing as a Graphviz digraph.
+    Dot,
+}
+
+impl Configurable<Self> for OutputFormat {
+    /// Get a potential `OutputFormat` variant from [ArgMatches].
+    ///
+    /// If one of the format names is passed, the corresponding `OutputFormat` variant is
+    /// returned in a [Some]. If none of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("output-format") > 0 {
+            match matches.value_of("output-format") {
+                Some("default") => Some(Self::Default),
+                Some("dot") => Some(Self::Dot),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `OutputFormat` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "output-format" and it names one of the formats, this returns the corresponding
+    /// `OutputFormat` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["output-format"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "default" => Some(Self::Default),
+                    "dot" => Some(Self::Dot),
+                    _ => {
+                        config.print_invalid_value_warning("output-format", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("output-format", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `OutputFormat` is [OutputFormat::Default].
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OutputFormat;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, OutputFormat::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_dot() {
+        let argv = vec!["lsd", "--output-format", "dot"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(OutputFormat::Dot),
+            OutputFormat::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, OutputFormat::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_dot() {
+        let yaml_string = "output-format: dot";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(OutputFormat::Dot),
+            OutputFormat::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "output-format: svg";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, OutputFormat::from_config(&Config::with_yaml(yaml)));
+    }
+}
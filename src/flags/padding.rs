@@ -0,0 +1,136 @@
+//! This module defines the [NumericPadding] flag. To set it up from [ArgMatches], a [Yaml]
+//! and its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which character pads the numeric columns.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum NumericPadding {
+    /// The variant to pad with plain spaces.
+    Space,
+    /// The variant to pad with figure spaces, which keep their digit width in proportional
+    /// fonts, so pasted listings stay aligned.
+    FigureSpace,
+    /// The variant to pad with leading zeros.
+    Zeros,
+}
+
+impl NumericPadding {
+    /// The character filling the padding.
+    pub fn fill(self) -> char {
+        match self {
+            Self::Space => ' ',
+            Self::FigureSpace => '\u{2007}',
+            Self::Zeros => '0',
+        }
+    }
+}
+
+impl Configurable<Self> for NumericPadding {
+    /// Get a potential `NumericPadding` variant from [ArgMatches].
+    ///
+    /// If one of the padding names is passed, the corresponding `NumericPadding` variant is
+    /// returned in a [Some]. If none of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("padding") > 0 {
+            match matches.value_of("padding") {
+                Some("space") => Some(Self::Space),
+                Some("figure-space") => Some(Self::FigureSpace),
+                Some("zeros") => Some(Self::Zeros),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `NumericPadding` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "padding" and it names one of the paddings, this returns the corresponding
+    /// `NumericPadding` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["padding"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "space" => Some(Self::Space),
+                    "figure-space" => Some(Self::FigureSpace),
+                    "zeros" => Some(Self::Zeros),
+                    _ => {
+                        config.print_invalid_value_warning("padding", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("padding", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `NumericPadding` is [NumericPadding::Space].
+impl Default for NumericPadding {
+    fn default() -> Self {
+        Self::Space
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NumericPadding;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NumericPadding::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_figure_space() {
+        let argv = vec!["lsd", "--padding", "figure-space"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(NumericPadding::FigureSpace),
+            NumericPadding::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NumericPadding::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_zeros() {
+        let yaml_string = "padding: zeros";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(NumericPadding::Zeros),
+            NumericPadding::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "padding: tabs";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, NumericPadding::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,202 @@
+//! This module defines the [PermissionFlag]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which representation of the permissions to use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum PermissionFlag {
+    /// The variant to show the permissions as an `rwx` string.
+    Rwx,
+    /// The variant to show the permissions as octal digits.
+    Octal,
+    /// The variant to show the `rwx` string with a space between the user, group and other
+    /// triads.
+    Spaced,
+    /// The variant to show the Windows file attributes. On other platforms this falls back to
+    /// the `rwx` representation.
+    Attributes,
+    /// The variant to show a placeholder instead of the permissions.
+    Disable,
+}
+
+impl Configurable<Self> for PermissionFlag {
+    /// Get a potential `PermissionFlag` variant from [ArgMatches].
+    ///
+    /// If any of the "rwx", "octal", "spaced", "attributes" or "disable" arguments is passed, the corresponding
+    /// `PermissionFlag` variant is returned in a [Some]. If neither of them is passed, this
+    /// returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("permission") > 0 {
+            match matches.value_of("permission") {
+                Some("rwx") => Some(Self::Rwx),
+                Some("octal") => Some(Self::Octal),
+                Some("spaced") => Some(Self::Spaced),
+                Some("attributes") => Some(Self::Attributes),
+                Some("disable") => Some(Self::Disable),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `PermissionFlag` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by "permission"
+    /// and it is either "rwx", "octal", "spaced", "attributes" or "disable", this returns the corresponding
+    /// `PermissionFlag` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["permission"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "rwx" => Some(Self::Rwx),
+                    "octal" => Some(Self::Octal),
+                    "spaced" => Some(Self::Spaced),
+                    "attributes" => Some(Self::Attributes),
+                    "disable" => Some(Self::Disable),
+                    _ => {
+                        config.print_invalid_value_warning("permission", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("permission", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `PermissionFlag` is [PermissionFlag::Rwx], except on Windows where the
+/// file attributes are the native representation.
+impl Default for PermissionFlag {
+    #[cfg(not(windows))]
+    fn default() -> Self {
+        Self::Rwx
+    }
+
+    #[cfg(windows)]
+    fn default() -> Self {
+        Self::Attributes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PermissionFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, PermissionFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_rwx() {
+        let argv = vec!["lsd", "--permission", "rwx"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_octal() {
+        let argv = vec!["lsd", "--permission", "octal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_spaced() {
+        let argv = vec!["lsd", "--permission", "spaced"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Spaced),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_attributes() {
+        let argv = vec!["lsd", "--permission", "attributes"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Attributes),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_disable() {
+        let argv = vec!["lsd", "--permission", "disable"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Disable),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_rwx() {
+        let yaml_string = "permission: rwx";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_octal() {
+        let yaml_string = "permission: octal";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_attributes() {
+        let yaml_string = "permission: attributes";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(PermissionFlag::Attributes),
+            PermissionFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! This module defines the [Print0Fields] struct. To set it up from [ArgMatches], a [Yaml]
+//! and its [Default] value, use its [configure_from](Print0Fields::configure_from) method.
+
+use std::convert::TryFrom;
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// A struct to hold the fields of the NUL-separated record output and to provide methods to
+/// create it. An empty [Vec] means the record output is disabled.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Print0Fields(pub Vec<Print0Field>);
+
+impl Print0Fields {
+    /// Returns a value from either [ArgMatches], a [Config] or a default value. The first
+    /// value that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](Print0Fields::from_arg_matches)
+    /// - [from_config](Print0Fields::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// This errors if any of the [ArgMatches] parameter arguments causes [Print0Field]'s
+    /// implementation of [TryFrom::try_from] to return an [Err].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if let Some(value) = Self::from_config(config) {
+            result = Ok(value);
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `Print0Fields` struct from [ArgMatches].
+    ///
+    /// If the "print0-fields" argument is passed, this returns a `Print0Fields` containing
+    /// the parameter values in a [Some]. Otherwise this returns [None].
+    ///
+    /// # Errors
+    ///
+    /// This errors if any of the parameter arguments causes [Print0Field]'s implementation of
+    /// [TryFrom::try_from] to return an [Err].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if matches.occurrences_of("print0-fields") > 0 {
+            if let Some(values) = matches.values_of("print0-fields") {
+                let mut fields: Vec<Print0Field> = vec![];
+                for value in values {
+                    match Print0Field::try_from(value) {
+                        Ok(field) => fields.push(field),
+                        Err(message) => {
+                            return Some(Err(Error::with_description(
+                                &message,
+                                ErrorKind::ValueValidation,
+                            )))
+                        }
+                    }
+                }
+                Some(Ok(Self(fields)))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Print0Fields` struct from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by
+    /// "print0-fields", each of its [String](Yaml::String) values is returned in a
+    /// `Print0Fields` in a [Some]. Otherwise it returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["print0-fields"] {
+                Yaml::BadValue => None,
+                Yaml::Array(values) => {
+                    let mut fields: Vec<Print0Field> = vec![];
+                    for value in values {
+                        match value {
+                            Yaml::String(value) => match Print0Field::try_from(value.as_ref()) {
+                                Ok(field) => fields.push(field),
+                                Err(_) => config.print_invalid_value_warning(
+                                    "print0-fields",
+                                    &value.to_string(),
+                                ),
+                            },
+                            _ => config.print_wrong_type_warning("print0-fields", "string"),
+                        }
+                    }
+                    Some(Self(fields))
+                }
+                _ => {
+                    config.print_wrong_type_warning("print0-fields", "array");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// A field of the NUL-separated record output.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum Print0Field {
+    Name,
+    Path,
+    Size,
+    Mtime,
+    Type,
+}
+
+impl TryFrom<&str> for Print0Field {
+    type Error = String;
+
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        match string {
+            "name" => Ok(Self::Name),
+            "path" => Ok(Self::Path),
+            "size" => Ok(Self::Size),
+            "mtime" => Ok(Self::Mtime),
+            "type" => Ok(Self::Type),
+            _ => Err(format!("Not a valid print0 field: {}", string)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_print0_fields {
+    use super::{Print0Field, Print0Fields};
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_configure_from_without_arguments() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Print0Fields::default(),
+            Print0Fields::configure_from(&matches, &Config::with_none()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_args() {
+        let argv = vec!["lsd", "--print0-fields", "name,size,mtime"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Print0Fields(vec![
+                Print0Field::Name,
+                Print0Field::Size,
+                Print0Field::Mtime
+            ]),
+            Print0Fields::configure_from(&matches, &Config::with_none()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_configure_from_config() {
+        let yaml_string = "print0-fields:\n  - path\n  - type";
+        let yaml = &YamlLoader::load_from_str(yaml_string).unwrap()[0];
+        assert_eq!(
+            Print0Fields(vec![Print0Field::Path, Print0Field::Type]),
+            Print0Fields::configure_from(
+                &app::build().get_matches_from_safe(vec!["lsd"]).unwrap(),
+                &Config::with_yaml(yaml.clone())
+            )
+            .unwrap()
+        );
+    }
+}
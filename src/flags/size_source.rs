@@ -0,0 +1,139 @@
+//! This module defines the [SizeSource]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing whether the size column reports the apparent file size or the allocated
+/// disk blocks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum SizeSource {
+    /// The variant to report the apparent file size.
+    Apparent,
+    /// The variant to report the allocated disk blocks, like `du`.
+    Blocks,
+}
+
+impl Configurable<Self> for SizeSource {
+    /// Get a potential `SizeSource` variant from [ArgMatches].
+    ///
+    /// If either the "apparent" or "blocks" argument is passed, the corresponding
+    /// `SizeSource` variant is returned in a [Some]. If neither of them is passed, this
+    /// returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("size-source") > 0 {
+            match matches.value_of("size-source") {
+                Some("apparent") => Some(Self::Apparent),
+                Some("blocks") => Some(Self::Blocks),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SizeSource` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "size-source" and it is either "apparent" or "blocks", this returns the corresponding
+    /// `SizeSource` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["size-source"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "apparent" => Some(Self::Apparent),
+                    "blocks" => Some(Self::Blocks),
+                    _ => {
+                        config.print_invalid_value_warning("size-source", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("size-source", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `SizeSource` is [SizeSource::Apparent].
+impl Default for SizeSource {
+    fn default() -> Self {
+        Self::Apparent
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizeSource;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SizeSource::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_apparent() {
+        let argv = vec!["lsd", "--size-source", "apparent"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SizeSource::Apparent),
+            SizeSource::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_blocks() {
+        let argv = vec!["lsd", "--size-source", "blocks"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SizeSource::Blocks),
+            SizeSource::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeSource::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SizeSource::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_blocks() {
+        let yaml_string = "size-source: blocks";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SizeSource::Blocks),
+            SizeSource::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "size-source: du";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SizeSource::from_config(&Config::with_yaml(yaml)));
+    }
+}
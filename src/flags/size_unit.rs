@@ -0,0 +1,152 @@
+//! This module defines the [SizeUnitFlag]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which prefix system the size units use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum SizeUnitFlag {
+    /// The variant to keep the historic labels: powers of 1024 written as KB and MB.
+    Default,
+    /// The variant to use binary prefixes: powers of 1024 written as KiB and MiB.
+    Binary,
+    /// The variant to use decimal prefixes: powers of 1000 written as KB and MB.
+    Decimal,
+}
+
+impl Configurable<Self> for SizeUnitFlag {
+    /// Get a potential `SizeUnitFlag` variant from [ArgMatches].
+    ///
+    /// If any of the "default", "binary" or "decimal" arguments is passed, the corresponding
+    /// `SizeUnitFlag` variant is returned in a [Some]. If neither of them is passed, this
+    /// returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("size-unit") > 0 {
+            match matches.value_of("size-unit") {
+                Some("default") => Some(Self::Default),
+                Some("binary") => Some(Self::Binary),
+                Some("decimal") => Some(Self::Decimal),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SizeUnitFlag` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "size-unit" and it is either "default", "binary" or "decimal", this returns the
+    /// corresponding `SizeUnitFlag` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["size-unit"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "default" => Some(Self::Default),
+                    "binary" => Some(Self::Binary),
+                    "decimal" => Some(Self::Decimal),
+                    _ => {
+                        config.print_invalid_value_warning("size-unit", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("size-unit", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `SizeUnitFlag` is [SizeUnitFlag::Default].
+impl Default for SizeUnitFlag {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizeUnitFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SizeUnitFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_binary() {
+        let argv = vec!["lsd", "--size-unit", "binary"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SizeUnitFlag::Binary),
+            SizeUnitFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_decimal() {
+        let argv = vec!["lsd", "--size-unit", "decimal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SizeUnitFlag::Decimal),
+            SizeUnitFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SizeUnitFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SizeUnitFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_binary() {
+        let yaml_string = "size-unit: binary";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SizeUnitFlag::Binary),
+            SizeUnitFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_decimal() {
+        let yaml_string = "size-unit: decimal";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SizeUnitFlag::Decimal),
+            SizeUnitFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "size-unit: metric";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SizeUnitFlag::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -14,21 +14,30 @@ pub struct Sorting {
     pub column: SortColumn,
     pub order: SortOrder,
     pub dir_grouping: DirGrouping,
+    pub symlink_grouping: SymlinkGrouping,
+    pub natural: NaturalSort,
+    pub collate: Collate,
 }
 
 impl Sorting {
     /// Get a `Sorting` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
-    /// [Configurable] implementation.
+    /// The [SortColumn], [SortOrder], [DirGrouping], [SymlinkGrouping], [NaturalSort] and
+    /// [Collate] are configured with their respective [Configurable] implementation.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let column = SortColumn::configure_from(matches, config);
         let order = SortOrder::configure_from(matches, config);
         let dir_grouping = DirGrouping::configure_from(matches, config);
+        let symlink_grouping = SymlinkGrouping::configure_from(matches, config);
+        let natural = NaturalSort::configure_from(matches, config);
+        let collate = Collate::configure_from(matches, config);
         Self {
             column,
             order,
             dir_grouping,
+            symlink_grouping,
+            natural,
+            collate,
         }
     }
 }
@@ -37,9 +46,11 @@ impl Sorting {
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum SortColumn {
     Extension,
+    INode,
     Name,
     Time,
     Size,
+    Type,
     Version,
 }
 
@@ -58,6 +69,10 @@ impl Configurable<Self> for SortColumn {
             Some(Self::Extension)
         } else if matches.is_present("versionsort") || sort == Some("version") {
             Some(Self::Version)
+        } else if sort == Some("type") {
+            Some(Self::Type)
+        } else if sort == Some("inode") {
+            Some(Self::INode)
         } else {
             None
         }
@@ -77,6 +92,8 @@ impl Configurable<Self> for SortColumn {
                     "name" => Some(Self::Name),
                     "time" => Some(Self::Time),
                     "size" => Some(Self::Size),
+                    "inode" => Some(Self::INode),
+                    "type" => Some(Self::Type),
                     "version" => Some(Self::Version),
                     _ => {
                         config.print_invalid_value_warning("sorting->column", &value);
@@ -226,6 +243,41 @@ impl Default for DirGrouping {
     }
 }
 
+/// The flag showing whether the name sort compares embedded numbers by their value, so
+/// `file2` sorts before `file10`.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct NaturalSort(pub bool);
+
+impl Configurable<Self> for NaturalSort {
+    /// Get a potential `NaturalSort` value from [ArgMatches].
+    ///
+    /// The natural ordering is configured through the configuration file only, so this
+    /// always returns [None].
+    fn from_arg_matches(_matches: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `NaturalSort` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by
+    /// "sorting" -> "natural", this returns its value as the value of the `NaturalSort`, in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["sorting"]["natural"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("sorting->natural", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_sort_column {
     use super::SortColumn;
@@ -563,3 +615,336 @@ mod test_dir_grouping {
         );
     }
 }
+
+/// The flag showing where to place symlinks.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum SymlinkGrouping {
+    None,
+    First,
+    Last,
+}
+
+impl Configurable<Self> for SymlinkGrouping {
+    /// Get a potential `SymlinkGrouping` variant from [ArgMatches].
+    ///
+    /// If the "classic" argument is passed, then this returns the [SymlinkGrouping::None]
+    /// variant in a [Some]. Otherwise if the argument is passed, this returns the variant
+    /// corresponding to its parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("classic") {
+            Some(Self::None)
+        } else if matches.occurrences_of("group-symlinks") > 0 {
+            match matches.value_of("group-symlinks") {
+                Some("first") => Some(Self::First),
+                Some("last") => Some(Self::Last),
+                Some("none") => Some(Self::None),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SymlinkGrouping` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by
+    /// "classic" and its value is `true`, then this returns the [SymlinkGrouping::None]
+    /// variant in a [Some]. Otherwise if the Yaml contains a [String](Yaml::String) value
+    /// pointed to by "sorting" -> "symlink-grouping" and it is one of "first", "last" or
+    /// "none", this returns its corresponding variant in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            if let Yaml::Boolean(true) = &yaml["classic"] {
+                Some(Self::None)
+            } else {
+                match &yaml["sorting"]["symlink-grouping"] {
+                    Yaml::BadValue => None,
+                    Yaml::String(value) => match value.as_ref() {
+                        "first" => Some(Self::First),
+                        "last" => Some(Self::Last),
+                        "none" => Some(Self::None),
+                        _ => {
+                            config.print_invalid_value_warning("sorting->symlink-grouping", &value);
+                            None
+                        }
+                    },
+                    _ => {
+                        config.print_wrong_type_warning("sorting->symlink-grouping", "string");
+                        None
+                    }
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `SymlinkGrouping` is [SymlinkGrouping::None].
+impl Default for SymlinkGrouping {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// The flag showing how the name sort compares names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Collate {
+    /// The variant to compare names bytewise, independent of any locale.
+    Byte,
+    /// The variant to compare names with the collation rules of the user's locale, so
+    /// accented and non-Latin names sort the way the locale expects.
+    Locale,
+}
+
+impl Configurable<Self> for Collate {
+    /// Get a potential `Collate` variant from [ArgMatches].
+    ///
+    /// The collation mode is configured through the configuration file only, so this always
+    /// returns [None].
+    fn from_arg_matches(_matches: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `Collate` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by
+    /// "sorting" -> "collate" and it is either "locale" or "byte", this returns the
+    /// corresponding `Collate` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["sorting"]["collate"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "byte" => Some(Self::Byte),
+                    "locale" => Some(Self::Locale),
+                    _ => {
+                        config.print_invalid_value_warning("sorting->collate", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("sorting->collate", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `Collate` is [Collate::Byte].
+impl Default for Collate {
+    fn default() -> Self {
+        Self::Byte
+    }
+}
+
+#[cfg(test)]
+mod test_symlink_grouping {
+    use super::SymlinkGrouping;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkGrouping::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_first() {
+        let argv = vec!["lsd", "--group-symlinks", "first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkGrouping::First),
+            SymlinkGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_last() {
+        let argv = vec!["lsd", "--group-symlinks", "last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkGrouping::Last),
+            SymlinkGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_explicit_none() {
+        let argv = vec!["lsd", "--group-symlinks", "none"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkGrouping::None),
+            SymlinkGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_classic_mode() {
+        let argv = vec!["lsd", "--group-symlinks", "first", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkGrouping::None),
+            SymlinkGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkGrouping::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SymlinkGrouping::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_first() {
+        let yaml_string = "sorting:\n  symlink-grouping: first";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SymlinkGrouping::First),
+            SymlinkGrouping::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_last() {
+        let yaml_string = "sorting:\n  symlink-grouping: last";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SymlinkGrouping::Last),
+            SymlinkGrouping::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let yaml_string = "classic: true\nsorting:\n  symlink-grouping: first";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SymlinkGrouping::None),
+            SymlinkGrouping::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_natural_sort {
+    use super::NaturalSort;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, NaturalSort::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, NaturalSort::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, NaturalSort::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "sorting:\n  natural: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(NaturalSort(true)),
+            NaturalSort::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "sorting:\n  natural: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(NaturalSort(false)),
+            NaturalSort::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_collate {
+    use super::Collate;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Collate::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Collate::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Collate::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_byte() {
+        let yaml_string = "sorting:\n  collate: byte";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Collate::Byte),
+            Collate::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_locale() {
+        let yaml_string = "sorting:\n  collate: locale";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Collate::Locale),
+            Collate::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "sorting:\n  collate: icu";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Collate::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,174 @@
+//! This module defines the [ThemeFlag]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing which color palette to use.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum ThemeFlag {
+    /// The variant to pick the palette based on the terminal background, using the `COLORFGBG`
+    /// environment variable when it is set.
+    Auto,
+    /// The variant to use the default palette, tuned for dark backgrounds.
+    Default,
+    /// The variant to use the palette tuned for light backgrounds.
+    Light,
+    /// The variant to use the palette restricted to the bright half of the classic sixteen
+    /// color palette, for high contrast.
+    HighContrast,
+    /// The variant to use the palette avoiding red-green distinctions, for deuteranopia.
+    Deuteranopia,
+}
+
+impl Configurable<Self> for ThemeFlag {
+    /// Get a potential `ThemeFlag` variant from [ArgMatches].
+    ///
+    /// If one of the theme names is passed, the corresponding `ThemeFlag` variant is returned
+    /// in a [Some]. If none of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("theme") > 0 {
+            match matches.value_of("theme") {
+                Some("auto") => Some(Self::Auto),
+                Some("default") => Some(Self::Default),
+                Some("light") => Some(Self::Light),
+                Some("high-contrast") => Some(Self::HighContrast),
+                Some("deuteranopia") => Some(Self::Deuteranopia),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ThemeFlag` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by "theme"
+    /// and it names one of the themes, this returns the corresponding `ThemeFlag` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["theme"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "auto" => Some(Self::Auto),
+                    "default" => Some(Self::Default),
+                    "light" => Some(Self::Light),
+                    "high-contrast" => Some(Self::HighContrast),
+                    "deuteranopia" => Some(Self::Deuteranopia),
+                    _ => {
+                        config.print_invalid_value_warning("theme", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("theme", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `ThemeFlag` is [ThemeFlag::Auto].
+impl Default for ThemeFlag {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ThemeFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ThemeFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_auto() {
+        let argv = vec!["lsd", "--theme", "auto"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeFlag::Auto),
+            ThemeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_default() {
+        let argv = vec!["lsd", "--theme", "default"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeFlag::Default),
+            ThemeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_light() {
+        let argv = vec!["lsd", "--theme", "light"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeFlag::Light),
+            ThemeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_high_contrast() {
+        let argv = vec!["lsd", "--theme", "high-contrast"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeFlag::HighContrast),
+            ThemeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_deuteranopia() {
+        let argv = vec!["lsd", "--theme", "deuteranopia"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ThemeFlag::Deuteranopia),
+            ThemeFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ThemeFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ThemeFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_light() {
+        let yaml_string = "theme: light";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(ThemeFlag::Light),
+            ThemeFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
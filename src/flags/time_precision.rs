@@ -0,0 +1,154 @@
+//! This module defines the [TimePrecision] flag. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing with which precision to render timestamp seconds.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum TimePrecision {
+    /// The variant to render whole seconds.
+    Second,
+    /// The variant to render seconds with three fractional digits.
+    Millisecond,
+    /// The variant to render seconds with nine fractional digits.
+    Nanosecond,
+}
+
+impl Configurable<Self> for TimePrecision {
+    /// Get a potential `TimePrecision` variant from [ArgMatches].
+    ///
+    /// If the "time-precision" argument is passed, this returns the corresponding
+    /// `TimePrecision` variant in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("time-precision") > 0 {
+            match matches.value_of("time-precision") {
+                Some("s") => Some(Self::Second),
+                Some("ms") => Some(Self::Millisecond),
+                Some("ns") => Some(Self::Nanosecond),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TimePrecision` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "time-precision" and it is either "s", "ms" or "ns", this returns the corresponding
+    /// `TimePrecision` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["time-precision"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "s" => Some(Self::Second),
+                    "ms" => Some(Self::Millisecond),
+                    "ns" => Some(Self::Nanosecond),
+                    _ => {
+                        config.print_invalid_value_warning("time-precision", &value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("time-precision", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `TimePrecision` is [TimePrecision::Second].
+impl Default for TimePrecision {
+    fn default() -> Self {
+        Self::Second
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimePrecision;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TimePrecision::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_second() {
+        let argv = vec!["lsd", "--time-precision", "s"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimePrecision::Second),
+            TimePrecision::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_millisecond() {
+        let argv = vec!["lsd", "--time-precision", "ms"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimePrecision::Millisecond),
+            TimePrecision::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_nanosecond() {
+        let argv = vec!["lsd", "--time-precision", "ns"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimePrecision::Nanosecond),
+            TimePrecision::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TimePrecision::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, TimePrecision::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_millisecond() {
+        let yaml_string = "time-precision: ms";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(TimePrecision::Millisecond),
+            TimePrecision::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_nanosecond() {
+        let yaml_string = "time-precision: ns";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(TimePrecision::Nanosecond),
+            TimePrecision::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
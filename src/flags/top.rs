@@ -0,0 +1,151 @@
+//! This module defines the [Top] option. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Top::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The number of largest files the listing is limited to. [None] keeps the default of
+/// listing everything.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Top(pub Option<usize>);
+
+impl Top {
+    /// Get the Top from either [ArgMatches], a [Config] or the [Default] value. The first
+    /// value that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](Top::from_arg_matches)
+    /// - [from_config](Top::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If the parameter to the "top" argument can not be parsed, this returns an [Error].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = Ok(value);
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `Top` from [ArgMatches].
+    ///
+    /// If the "top" argument is passed, its parameter is evaluated. If it can be parsed into
+    /// a [usize], the [Result] is returned in the [Some]. If it can not be parsed an [Error]
+    /// is returned in the [Some]. If the argument has not been passed, a [None] is returned.
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if let Some(str) = matches.value_of("top") {
+            match str.parse::<usize>() {
+                Ok(value) if value > 0 => return Some(Ok(Self(Some(value)))),
+                _ => {
+                    return Some(Err(Error::with_description(
+                        "The argument '--top' requires a valid number greater than zero.",
+                        ErrorKind::ValueValidation,
+                    )))
+                }
+            }
+        }
+        None
+    }
+
+    /// Get a potential `Top` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a positive [Integer](Yaml::Integer) value pointed to
+    /// by "top", this returns its value in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["top"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) => {
+                    if *value > 0 {
+                        Some(Self(Some(*value as usize)))
+                    } else {
+                        config.print_warning("The top value has to be greater than zero.");
+                        None
+                    }
+                }
+                _ => {
+                    config.print_wrong_type_warning("top", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Top;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use clap::ErrorKind;
+    use yaml_rust::YamlLoader;
+
+    // The from_arg_matches tests are implemented using match expressions instead of the
+    // assert_eq macro, because clap::Error does not implement PartialEq.
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Top::from_arg_matches(&matches) {
+            None => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_integer() {
+        let argv = vec!["lsd", "--top", "20"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Top::from_arg_matches(&matches) {
+            Some(Ok(Top(Some(20)))) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--top", "0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Top::from_arg_matches(&matches) {
+            Some(Err(error)) => error.kind == ErrorKind::ValueValidation,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Top::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_integer() {
+        let yaml_string = "top: 20";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Top(Some(20))),
+            Top::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_neg_integer() {
+        let yaml_string = "top: -20";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Top::from_config(&Config::with_yaml(yaml)));
+    }
+}
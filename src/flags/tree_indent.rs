@@ -0,0 +1,144 @@
+//! This module defines the [TreeIndent] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](TreeIndent::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::{ArgMatches, Error, ErrorKind};
+use yaml_rust::Yaml;
+
+/// The flag showing whether to replace the branch characters of the tree layout with plain
+/// indentation and if so, how many spaces wide one indentation level is.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TreeIndent(pub Option<usize>);
+
+impl TreeIndent {
+    /// Get the TreeIndent from either [ArgMatches], a [Config] or the [Default] value. The first
+    /// value that is not [None] is used. The order of precedence for the value used is:
+    /// - [from_arg_matches](TreeIndent::from_arg_matches)
+    /// - [from_config](TreeIndent::from_config)
+    /// - [Default::default]
+    ///
+    /// # Errors
+    ///
+    /// If [from_arg_matches](TreeIndent::from_arg_matches) returns an [Error], this returns it.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let mut result: Result<Self, Error> = Ok(Default::default());
+
+        if config.has_yaml() {
+            if let Some(value) = Self::from_config(config) {
+                result = Ok(value);
+            }
+        }
+
+        if let Some(value) = Self::from_arg_matches(matches) {
+            result = value;
+        }
+
+        result
+    }
+
+    /// Get a potential `TreeIndent` value from [ArgMatches].
+    ///
+    /// If the "tree-indent" argument is passed, its parameter is parsed into the width of one
+    /// indentation level and returned in a [Some]. Otherwise this returns [None].
+    ///
+    /// # Errors
+    ///
+    /// If the parameter can not be parsed into a [usize], this returns an [Error] of kind
+    /// [ValueValidation](ErrorKind::ValueValidation).
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Self, Error>> {
+        if matches.occurrences_of("tree-indent") > 0 {
+            let value = matches.value_of("tree-indent")?;
+            match value.parse::<usize>() {
+                Ok(width) => Some(Ok(Self(Some(width)))),
+                Err(_) => Some(Err(Error::with_description(
+                    "The argument '--tree-indent' requires a valid positive number.",
+                    ErrorKind::ValueValidation,
+                ))),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TreeIndent` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Integer](Yaml::Integer) value pointed to by
+    /// "tree-indent", this returns its value as the width of one indentation level, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["tree-indent"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) if *value >= 0 => Some(Self(Some(*value as usize))),
+                _ => {
+                    config.print_wrong_type_warning("tree-indent", "positive integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TreeIndent;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_configure_from_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = TreeIndent::configure_from(&matches, &Config::with_none());
+        assert!(matches!(result, Ok(TreeIndent(None))));
+    }
+
+    #[test]
+    fn test_configure_from_arg_matches() {
+        let argv = vec!["lsd", "--tree-indent", "4"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = TreeIndent::configure_from(&matches, &Config::with_none());
+        assert!(matches!(result, Ok(TreeIndent(Some(4)))));
+    }
+
+    #[test]
+    fn test_configure_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--tree-indent", "foo"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = TreeIndent::configure_from(&matches, &Config::with_none());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_configure_from_empty_config() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml = YamlLoader::load_from_str("---").unwrap()[0].clone();
+        let result = TreeIndent::configure_from(&matches, &Config::with_yaml(yaml));
+        assert!(matches!(result, Ok(TreeIndent(None))));
+    }
+
+    #[test]
+    fn test_configure_from_config() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml = YamlLoader::load_from_str("tree-indent: 2").unwrap()[0].clone();
+        let result = TreeIndent::configure_from(&matches, &Config::with_yaml(yaml));
+        assert!(matches!(result, Ok(TreeIndent(Some(2)))));
+    }
+
+    #[test]
+    fn test_configure_from_config_overwritten_by_args() {
+        let argv = vec!["lsd", "--tree-indent", "8"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml = YamlLoader::load_from_str("tree-indent: 2").unwrap()[0].clone();
+        let result = TreeIndent::configure_from(&matches, &Config::with_yaml(yaml));
+        assert!(matches!(result, Ok(TreeIndent(Some(8)))));
+    }
+}
@@ -0,0 +1,147 @@
+//! This module defines the [UidMap] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag holding the id ranges to map user and group ids through before display.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct UidMap(pub Vec<IdRange>);
+
+/// One range of a `uid_map`-style id mapping, in the format used by
+/// `/proc/[pid]/uid_map`: the first id inside the namespace, the first id outside of it and
+/// the length of the range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdRange {
+    pub inside: u32,
+    pub outside: u32,
+    pub count: u32,
+}
+
+impl UidMap {
+    /// Parse the contents of a `uid_map`-style file at `path`. Lines which do not hold three
+    /// ids are skipped, and an unreadable file yields an empty mapping.
+    fn from_file(path: &str) -> Self {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+
+        let ranges = contents
+            .lines()
+            .filter_map(|line| {
+                let mut columns = line.split_whitespace();
+                Some(IdRange {
+                    inside: columns.next()?.parse().ok()?,
+                    outside: columns.next()?.parse().ok()?,
+                    count: columns.next()?.parse().ok()?,
+                })
+            })
+            .collect();
+
+        Self(ranges)
+    }
+
+    /// Map an id seen outside the namespace to the corresponding id inside of it, if it falls
+    /// into one of the mapped ranges.
+    pub fn map(&self, id: u32) -> Option<u32> {
+        self.0
+            .iter()
+            .find(|range| range.outside <= id && id - range.outside < range.count)
+            .map(|range| range.inside + (id - range.outside))
+    }
+}
+
+impl Configurable<Self> for UidMap {
+    /// Get a potential `UidMap` value from [ArgMatches].
+    ///
+    /// If the "uid-map" argument is passed, this returns a `UidMap` parsed from the file it
+    /// points to in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("uid-map") > 0 {
+            matches.value_of("uid-map").map(Self::from_file)
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `UidMap` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "uid-map",
+    /// this returns a `UidMap` parsed from the file it points to in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["uid-map"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => Some(Self::from_file(value)),
+                _ => {
+                    config.print_wrong_type_warning("uid-map", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IdRange, UidMap};
+
+    use crate::app;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, UidMap::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_missing_file() {
+        let argv = vec!["lsd", "--uid-map", "/no/such/file"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(UidMap(Vec::new())), UidMap::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_map_inside_range() {
+        let map = UidMap(vec![IdRange {
+            inside: 0,
+            outside: 100_000,
+            count: 65_536,
+        }]);
+        assert_eq!(Some(1000), map.map(101_000));
+    }
+
+    #[test]
+    fn test_map_range_boundaries() {
+        let map = UidMap(vec![IdRange {
+            inside: 0,
+            outside: 100_000,
+            count: 65_536,
+        }]);
+        assert_eq!(Some(0), map.map(100_000));
+        assert_eq!(Some(65_535), map.map(165_535));
+        assert_eq!(None, map.map(165_536));
+    }
+
+    #[test]
+    fn test_map_outside_range() {
+        let map = UidMap(vec![IdRange {
+            inside: 0,
+            outside: 100_000,
+            count: 65_536,
+        }]);
+        assert_eq!(None, map.map(1000));
+    }
+
+    #[test]
+    fn test_map_empty() {
+        assert_eq!(None, UidMap::default().map(0));
+    }
+}
@@ -0,0 +1,119 @@
+//! This module defines the [WindowsAttributes] flag. To set it up from [ArgMatches], a [Yaml] and
+//! its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing whether entries carrying the Windows Hidden or System attribute are treated
+/// as hidden, like Explorer does.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct WindowsAttributes(pub bool);
+
+impl Configurable<Self> for WindowsAttributes {
+    /// Get a potential `WindowsAttributes` value from [ArgMatches].
+    ///
+    /// If the "no-windows-attributes" argument is passed, this returns a `WindowsAttributes` with
+    /// value `false` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("no-windows-attributes") {
+            Some(Self(false))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `WindowsAttributes` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "windows-attributes", this returns its value as the value of the `WindowsAttributes`, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["windows-attributes"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("windows-attributes", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `WindowsAttributes` is `true`, matching the visibility rules of the
+/// Windows desktop.
+impl Default for WindowsAttributes {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WindowsAttributes;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, WindowsAttributes::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_false() {
+        let argv = vec!["lsd", "--no-windows-attributes"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(WindowsAttributes(false)),
+            WindowsAttributes::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, WindowsAttributes::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            None,
+            WindowsAttributes::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "windows-attributes: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(WindowsAttributes(true)),
+            WindowsAttributes::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "windows-attributes: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(WindowsAttributes(false)),
+            WindowsAttributes::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -1,10 +1,13 @@
+use crate::flags::IconOverrides;
 use crate::meta::{FileType, Name};
 use std::collections::HashMap;
 
 pub struct Icons {
     display_icons: bool,
+    emoji: bool,
     icons_by_name: HashMap<&'static str, &'static str>,
     icons_by_extension: HashMap<&'static str, &'static str>,
+    overrides: IconOverrides,
     default_folder_icon: &'static str,
     default_file_icon: &'static str,
 }
@@ -14,6 +17,7 @@ pub enum Theme {
     NoIcon,
     Fancy,
     Unicode,
+    Emoji,
 }
 
 const ICON_SPACE: &str = " ";
@@ -24,7 +28,7 @@ const ICON_SPACE: &str = " ";
 // s#\\u[0-9a-f]*#\=eval('"'.submatch(0).'"')#
 impl Icons {
     pub fn new(theme: Theme) -> Self {
-        let display_icons = theme == Theme::Fancy || theme == Theme::Unicode;
+        let display_icons = theme != Theme::NoIcon;
         let (icons_by_name, icons_by_extension, default_file_icon, default_folder_icon) =
             if theme == Theme::Fancy {
                 (
@@ -44,13 +48,22 @@ impl Icons {
 
         Self {
             display_icons,
+            emoji: theme == Theme::Emoji,
             icons_by_name,
             icons_by_extension,
+            overrides: IconOverrides::default(),
             default_file_icon,
             default_folder_icon,
         }
     }
 
+    /// Layer the user icon mappings from the configuration file on top of the built-in
+    /// tables. They are consulted first, so they win over both the tables and the defaults.
+    pub fn with_overrides(mut self, overrides: IconOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
     pub fn get(&self, name: &Name) -> String {
         if !self.display_icons {
             return String::new();
@@ -59,7 +72,21 @@ impl Icons {
         // Check file types
         let file_type: FileType = name.file_type();
 
-        let icon = if let FileType::Directory { .. } = file_type {
+        let icon = if let Some(icon) = self.overrides.name.get(name.file_name()) {
+            // A user mapping for the exact name wins over everything, including the
+            // directory default, so directory names can be mapped as well.
+            icon.as_str()
+        } else if let Some(icon) = name
+            .extension()
+            .and_then(|extension| self.overrides.extension.get(extension))
+            .filter(|_| !matches!(file_type, FileType::Directory { .. }))
+        {
+            icon.as_str()
+        } else if self.emoji {
+            // A handful of category emoji instead of per-type glyphs, for terminals with
+            // emoji fonts but no Nerd Font.
+            Self::emoji_icon(&file_type)
+        } else if let FileType::Directory { .. } = file_type {
             self.default_folder_icon
         } else if let FileType::SymLink { is_dir: true } = file_type {
             "\u{f482}" // ""
@@ -92,6 +119,21 @@ impl Icons {
         format!("{}{}", icon, ICON_SPACE)
     }
 
+    /// The emoji for a file category, used by the emoji theme.
+    fn emoji_icon(file_type: &FileType) -> &'static str {
+        match file_type {
+            FileType::Directory { .. } => "\u{1f4c1}", // 📁
+            FileType::SymLink { .. } => "\u{1f517}", // 🔗
+            FileType::File { exec: true, .. } => "\u{2699}\u{fe0f}", // ⚙️
+            FileType::BlockDevice
+            | FileType::CharDevice
+            | FileType::Pipe
+            | FileType::Socket
+            | FileType::Special => "\u{2699}\u{fe0f}", // ⚙️
+            _ => "\u{1f4c4}", // 📄
+        }
+    }
+
     fn get_default_icons_by_name() -> HashMap<&'static str, &'static str> {
         let mut m = HashMap::new();
 
@@ -381,6 +423,31 @@ mod test {
         assert_eq!(icon, format!("{}{}", "\u{1f5cb}", ICON_SPACE));
     }
 
+    #[test]
+    fn get_default_file_icon_emoji() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon = Icons::new(Theme::Emoji);
+        let icon = icon.get(&meta.name);
+
+        assert_eq!(icon, format!("{}{}", "\u{1f4c4}", ICON_SPACE)); // 📄
+    }
+
+    #[test]
+    fn get_directory_icon_emoji() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path();
+        let meta = Meta::from_path(&file_path.to_path_buf(), false).unwrap();
+
+        let icon = Icons::new(Theme::Emoji);
+        let icon = icon.get(&meta.name);
+
+        assert_eq!(icon, format!("{}{}", "\u{1f4c1}", ICON_SPACE)); // 📁
+    }
+
     #[test]
     fn get_directory_icon() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
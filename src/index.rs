@@ -0,0 +1,244 @@
+//! This module implements a persistent index of directory sizes and entry counts, so that
+//! repeated `--total-size` listings of large trees do not have to walk them again.
+//!
+//! The index lives below the user's cache directory. `--total-size` consults it for
+//! directories whose modification time still matches and records fresh results for the ones
+//! that changed or were never measured, so the index builds itself up incrementally across
+//! runs. A directory's mtime only changes when its immediate entries change, so deeply
+//! nested modifications can go unnoticed until their parent directories are rebuilt;
+//! `lsd --index build PATH` forces a full refresh.
+
+use crate::print_error;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single indexed directory: its mtime in whole seconds at the time it was measured, its
+/// recursive size in bytes and the number of its immediate entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct IndexEntry {
+    mtime: i64,
+    size: u64,
+    entries: u64,
+}
+
+/// The persistent directory-size index, mapping canonical paths to their [IndexEntry].
+#[derive(Debug, Default)]
+struct SizeIndex {
+    entries: HashMap<PathBuf, IndexEntry>,
+    dirty: bool,
+}
+
+thread_local! {
+    /// The loaded index, if any. [None] means the index was disabled for this run by
+    /// [disable].
+    static INDEX: RefCell<Option<Option<SizeIndex>>> = RefCell::new(None);
+}
+
+/// Get the recursive size of the given directory, preferring the index when it holds a still
+/// valid entry and falling back to `compute` otherwise. Fresh results are recorded so the
+/// next run can skip the walk, starting an empty index when no cache file exists yet.
+pub fn total_size(path: &Path, compute: impl Fn(&PathBuf) -> u64) -> u64 {
+    let path_buf = path.to_path_buf();
+
+    INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        let index = index.get_or_insert_with(|| Some(load().unwrap_or_default()));
+
+        let index = match index {
+            Some(index) => index,
+            None => return compute(&path_buf),
+        };
+
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mtime = modification_time(&key);
+
+        if let Some(entry) = index.entries.get(&key) {
+            if Some(entry.mtime) == mtime {
+                return entry.size;
+            }
+        }
+
+        let size = compute(&path_buf);
+        if let Some(mtime) = mtime {
+            index.entries.insert(
+                key.clone(),
+                IndexEntry {
+                    mtime,
+                    size,
+                    entries: count_entries(&key),
+                },
+            );
+            index.dirty = true;
+        }
+
+        size
+    })
+}
+
+/// Disable the index for this run: no cache file is read or written and every size is
+/// computed fresh, as `--no-external` demands.
+pub fn disable() {
+    INDEX.with(|index| {
+        *index.borrow_mut() = Some(None);
+    });
+}
+
+/// Write the index back to its cache file, if it was loaded and modified during this run.
+pub fn flush() {
+    INDEX.with(|index| {
+        if let Some(Some(index)) = &*index.borrow() {
+            if index.dirty {
+                save(index);
+            }
+        }
+    });
+}
+
+/// Build the index for the given paths from scratch, recording every directory below them,
+/// and report what was indexed.
+pub fn build(paths: &[PathBuf]) {
+    let mut index = match load() {
+        Some(index) => index,
+        None => SizeIndex::default(),
+    };
+
+    let mut indexed = 0;
+    for path in paths {
+        build_directory(path, &mut index, &mut indexed);
+    }
+
+    index.dirty = true;
+    save(&index);
+
+    match index_file() {
+        Some(file) => println!("Indexed {} directories into {}", indexed, file.display()),
+        None => println!("Indexed {} directories", indexed),
+    }
+}
+
+/// Index one directory recursively, returning its total size in bytes.
+fn build_directory(path: &Path, index: &mut SizeIndex, indexed: &mut u64) -> u64 {
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            print_error!("lsd: {}: {}\n", path.display(), err);
+            return 0;
+        }
+    };
+
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    let mut size = metadata.len();
+    let mut entries = 0;
+    if let Ok(content) = fs::read_dir(path) {
+        for entry in content.flatten() {
+            entries += 1;
+            size += build_directory(&entry.path(), index, indexed);
+        }
+    }
+
+    let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Some(mtime) = modification_time(&key) {
+        index.entries.insert(
+            key,
+            IndexEntry {
+                mtime,
+                size,
+                entries,
+            },
+        );
+        *indexed += 1;
+    }
+
+    size
+}
+
+/// The path of the cache file holding the index.
+fn index_file() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache| cache.join("lsd").join("size-index"))
+}
+
+/// Load the index from its cache file. Returns [None] when the file does not exist.
+fn load() -> Option<SizeIndex> {
+    let content = fs::read_to_string(index_file()?).ok()?;
+
+    let mut entries = HashMap::new();
+    for line in content.lines() {
+        let mut fields = line.splitn(4, '\t');
+        if let (Some(mtime), Some(size), Some(count), Some(path)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        {
+            if let (Ok(mtime), Ok(size), Ok(count)) =
+                (mtime.parse(), size.parse(), count.parse())
+            {
+                entries.insert(
+                    PathBuf::from(path),
+                    IndexEntry {
+                        mtime,
+                        size,
+                        entries: count,
+                    },
+                );
+            }
+        }
+    }
+
+    Some(SizeIndex {
+        entries,
+        dirty: false,
+    })
+}
+
+/// Write the index to its cache file. Paths containing newlines can not be represented in the
+/// line based format and are skipped.
+fn save(index: &SizeIndex) {
+    let file = match index_file() {
+        Some(file) => file,
+        None => return,
+    };
+
+    if let Some(parent) = file.parent() {
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+
+    let mut content = String::new();
+    for (path, entry) in &index.entries {
+        let path = path.to_string_lossy();
+        if path.contains('\n') || path.contains('\t') {
+            continue;
+        }
+
+        content += &format!(
+            "{}\t{}\t{}\t{}\n",
+            entry.mtime, entry.size, entry.entries, path
+        );
+    }
+
+    if let Err(err) = fs::write(&file, content) {
+        print_error!("lsd: {}: {}\n", file.display(), err);
+    }
+}
+
+/// The modification time of the given path in whole seconds since the epoch.
+fn modification_time(path: &Path) -> Option<i64> {
+    let modified = path.metadata().ok()?.modified().ok()?;
+
+    match modified.duration_since(std::time::UNIX_EPOCH) {
+        Ok(duration) => Some(duration.as_secs() as i64),
+        Err(_) => None,
+    }
+}
+
+/// The number of immediate entries of the given directory.
+fn count_entries(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .map(|entries| entries.count() as u64)
+        .unwrap_or(0)
+}
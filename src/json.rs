@@ -0,0 +1,167 @@
+//! This module renders a listing of [Meta] as JSON, as an alternative to the visual layouts in
+//! [display](crate::display). The tree layout keeps its hierarchy through nested `children`
+//! arrays, while the other layouts flatten the visited entries into a single array.
+//!
+//! The output is a single object of the shape
+//!
+//! ```text
+//! {
+//!   "schema_version": 1,
+//!   "entries": [
+//!     { "name": "...", "path": "...", "type": "...", "size": 123, "children": [ ... ] }
+//!   ]
+//! }
+//! ```
+//!
+//! where `children` is only present in the tree layout. Each entry additionally carries the
+//! modification time (`mtime`, seconds since the epoch), the octal `permissions`, the `owner`
+//! and `group` names, and `btime`, `atime` and the symlink `target` when the filesystem
+//! records them. Scripts should check `schema_version`; it is only incremented when a field
+//! is renamed, removed or changes meaning, never for additions.
+
+use crate::flags::{Flags, Layout};
+use crate::meta::{FileType, Meta};
+
+/// The version of the structured output schema. Incremented on breaking changes only; adding
+/// new fields keeps the current version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Render the given metas as a JSON array, honoring the [Layout] in the [Flags].
+pub fn render(metas: &[Meta], flags: &Flags) -> String {
+    let mut output = format!("{{\"schema_version\":{},\"entries\":[", SCHEMA_VERSION);
+
+    if flags.layout == Layout::Tree {
+        append_entries(&mut output, metas, true);
+    } else {
+        let mut flat = Vec::new();
+        flatten(metas, &mut flat);
+        append_entries(&mut output, &flat, false);
+    }
+
+    output += "]}\n";
+    output
+}
+
+/// Collect every visited entry into a flat list, in traversal order.
+fn flatten<'a>(metas: &'a [Meta], flat: &mut Vec<&'a Meta>) {
+    for meta in metas {
+        flat.push(meta);
+        if let Some(content) = &meta.content {
+            flatten(content, flat);
+        }
+    }
+}
+
+fn append_entries<M: AsMeta>(output: &mut String, metas: &[M], nested: bool) {
+    for (index, meta) in metas.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+        append_entry(output, meta.as_meta(), nested);
+    }
+}
+
+fn append_entry(output: &mut String, meta: &Meta, nested: bool) {
+    output.push('{');
+    output.push_str(&format!(
+        "\"name\":{},\"path\":{},\"type\":\"{}\",\"size\":{},\"mtime\":{},\"permissions\":\"{}\",\"owner\":{},\"group\":{}",
+        escape(&meta.name.name),
+        escape(&meta.path.to_string_lossy()),
+        type_name(&meta.file_type),
+        meta.size.get_bytes(),
+        meta.date.unix_timestamp(),
+        meta.permissions.octal_string(),
+        escape(meta.owner.user()),
+        escape(meta.owner.group()),
+    ));
+
+    if let Some(created) = &meta.created {
+        output.push_str(&format!(",\"btime\":{}", created.unix_timestamp()));
+    }
+
+    if let Some(accessed) = &meta.accessed {
+        output.push_str(&format!(",\"atime\":{}", accessed.unix_timestamp()));
+    }
+
+    if let Some(target) = meta.symlink.symlink_string() {
+        output.push_str(&format!(",\"target\":{}", escape(&target)));
+    }
+
+    if nested {
+        if let Some(content) = &meta.content {
+            output.push_str(",\"children\":[");
+            append_entries(output, content, nested);
+            output.push(']');
+        }
+    }
+
+    output.push('}');
+}
+
+/// The `type` field value for each [FileType] variant, shared with the record output in
+/// [display](crate::display).
+pub fn type_name(file_type: &FileType) -> &'static str {
+    match file_type {
+        FileType::BlockDevice => "block_device",
+        FileType::CharDevice => "char_device",
+        FileType::Directory { .. } => "directory",
+        FileType::File { .. } => "file",
+        FileType::SymLink { .. } => "symlink",
+        FileType::Pipe => "pipe",
+        FileType::Socket => "socket",
+        FileType::Special => "special",
+    }
+}
+
+/// Quote and escape a string for inclusion in the JSON output.
+fn escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len() + 2);
+    escaped.push('"');
+    for character in input.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            control if (control as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", control as u32))
+            }
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Helper to render both owned and borrowed lists of [Meta] with the same code.
+trait AsMeta {
+    fn as_meta(&self) -> &Meta;
+}
+
+impl AsMeta for Meta {
+    fn as_meta(&self) -> &Meta {
+        self
+    }
+}
+
+impl AsMeta for &Meta {
+    fn as_meta(&self) -> &Meta {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::escape;
+
+    #[test]
+    fn test_escape_plain() {
+        assert_eq!("\"name.txt\"", escape("name.txt"));
+    }
+
+    #[test]
+    fn test_escape_quotes_and_controls() {
+        assert_eq!("\"a\\\"b\\\\c\\nd\"", escape("a\"b\\c\nd"));
+    }
+}
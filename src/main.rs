@@ -33,15 +33,20 @@ mod color;
 mod config_file;
 mod core;
 mod display;
+mod dot;
 mod flags;
 mod icon;
+mod index;
+mod json;
 mod meta;
 mod sort;
 
 use crate::config_file::Config;
 use crate::core::Core;
 use crate::flags::Flags;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// Macro used to avoid panicking when the lsd method is used with a pipe and
 /// stderr close before our program.
@@ -86,18 +91,229 @@ macro_rules! print_output {
     };
 }
 
+/// Print a deprecation warning for every argument using one of the
+/// [DEPRECATED_ALIASES](app::DEPRECATED_ALIASES) spellings. This scans the raw arguments,
+/// because clap does not record which alias matched.
+fn print_deprecation_warnings<I>(args: I)
+where
+    I: IntoIterator<Item = std::ffi::OsString>,
+{
+    for arg in args {
+        let arg = match arg.to_str() {
+            Some(arg) => arg,
+            None => continue,
+        };
+
+        for (old, new) in app::DEPRECATED_ALIASES {
+            if arg == format!("--{}", old) || arg.starts_with(&format!("--{}=", old)) {
+                print_error!(
+                    "lsd: the '--{}' flag is deprecated, use '--{}' instead\n",
+                    old,
+                    new
+                );
+            }
+        }
+    }
+}
+
+/// How many refreshes a change marker stays on a row before it fades out.
+const MARKER_REFRESHES: u8 = 3;
+
+/// The shallow state watch mode diffs between refreshes: every input and, for directory
+/// inputs, their direct entries, fingerprinted by modification time and size.
+fn watch_snapshot(inputs: &[PathBuf]) -> HashMap<PathBuf, (Option<SystemTime>, u64)> {
+    let mut snapshot = HashMap::new();
+    let mut record = |path: PathBuf| {
+        if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+            snapshot.insert(path, (metadata.modified().ok(), metadata.len()));
+        }
+    };
+
+    for input in inputs {
+        if input.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(input) {
+                for entry in entries.flatten() {
+                    record(entry.path());
+                }
+            }
+        } else {
+            record(input.clone());
+        }
+    }
+
+    snapshot
+}
+
+/// The line without its ANSI escape sequences, for matching rendered rows against entry
+/// names.
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip over a CSI sequence up to its final byte.
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        stripped.push(c);
+    }
+    stripped
+}
+
+/// Prefix every rendered row with a two character gutter: `+` on rows added since the
+/// previous refresh and `*` on modified ones. Entries which disappeared are no longer part
+/// of the listing, so they get their own `-` rows below it until their marker fades.
+fn annotate_changes(output: &str, markers: &HashMap<PathBuf, (char, u8)>) -> String {
+    let names: Vec<(String, char)> = markers
+        .iter()
+        .filter(|(_, (marker, _))| *marker != '-')
+        .filter_map(|(path, (marker, _))| {
+            path.file_name()
+                .map(|name| (name.to_string_lossy().into_owned(), *marker))
+        })
+        .collect();
+
+    let mut annotated = String::with_capacity(output.len());
+    for line in output.lines() {
+        let stripped = strip_ansi(line);
+        let marker = names
+            .iter()
+            .find(|(name, _)| stripped.split_whitespace().any(|word| word == name))
+            .map(|(_, marker)| *marker)
+            .unwrap_or(' ');
+        annotated.push(marker);
+        annotated.push(' ');
+        annotated.push_str(line);
+        annotated.push('\n');
+    }
+
+    for (path, (marker, _)) in markers {
+        if *marker == '-' {
+            annotated += &format!("- {}\n", path.display());
+        }
+    }
+
+    annotated
+}
+
+/// Redraw the listing every couple of seconds, with the rows which changed since the
+/// previous refresh marked in a gutter for a few refreshes. The configuration file is
+/// polled for changes and re-applied live, so tweaking a theme does not require
+/// relaunching between adjustments.
+fn watch(matches: &clap::ArgMatches, inputs: &[PathBuf]) -> ! {
+    const INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+    let read_config = || {
+        if matches.is_present("ignore-config") {
+            Config::with_none()
+        } else {
+            Config::read_config()
+        }
+    };
+
+    let modification_time = |config: &Config| {
+        config
+            .file
+            .as_ref()
+            .and_then(|file| std::fs::metadata(file).ok())
+            .and_then(|metadata| metadata.modified().ok())
+    };
+
+    let mut config = read_config();
+    let mut config_mtime = modification_time(&config);
+    let mut previous: Option<HashMap<PathBuf, (Option<SystemTime>, u64)>> = None;
+    let mut markers: HashMap<PathBuf, (char, u8)> = HashMap::new();
+    loop {
+        let mtime = modification_time(&config);
+        if mtime != config_mtime {
+            config = read_config();
+            config_mtime = mtime;
+        }
+
+        let flags = Flags::configure_from(&matches, &config).unwrap_or_else(|err| err.exit());
+
+        // Diff the shallow state against the previous refresh and start a marker for every
+        // entry which appeared, changed or disappeared since.
+        let snapshot = watch_snapshot(inputs);
+        if let Some(previous) = &previous {
+            for (path, fingerprint) in &snapshot {
+                match previous.get(path) {
+                    None => {
+                        markers.insert(path.clone(), ('+', MARKER_REFRESHES));
+                    }
+                    Some(old) if old != fingerprint => {
+                        markers.insert(path.clone(), ('*', MARKER_REFRESHES));
+                    }
+                    Some(_) => {}
+                }
+            }
+            for path in previous.keys() {
+                if !snapshot.contains_key(path) {
+                    markers.insert(path.clone(), ('-', MARKER_REFRESHES));
+                }
+            }
+        }
+        previous = Some(snapshot);
+
+        let output = Core::new(flags).render(inputs.to_vec());
+
+        // Clear the screen and move the cursor home before each redraw, like watch(1).
+        print_output!("\x1B[2J\x1B[1;1H");
+        if markers.is_empty() {
+            print_output!("{}", output);
+        } else {
+            print_output!("{}", annotate_changes(&output, &markers));
+        }
+
+        // Fade the markers out over the following refreshes.
+        markers.retain(|_, (_, age)| {
+            *age -= 1;
+            *age > 0
+        });
+
+        std::thread::sleep(INTERVAL);
+    }
+}
+
 fn main() {
+    print_deprecation_warnings(wild::args_os());
     let matches = app::build().get_matches_from(wild::args_os());
 
     // input translate glob FILE without single quote into real names
     // for example:
     // * to all files matched
     // '*' remain as '*'
-    let inputs = matches
-        .values_of("FILE")
-        .expect("failed to retrieve cli value")
-        .map(PathBuf::from)
-        .collect();
+    let inputs: Vec<PathBuf> = if matches.is_present("stdin") {
+        let mut input = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
+            .expect("failed to read stdin");
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    } else {
+        matches
+            .values_of("FILE")
+            .expect("failed to retrieve cli value")
+            .map(PathBuf::from)
+            .collect()
+    };
+
+    if matches.value_of("index") == Some("build") {
+        index::build(&inputs);
+        return;
+    }
+
+    if matches.is_present("watch") {
+        watch(&matches, &inputs);
+    }
 
     let config = if matches.is_present("ignore-config") {
         Config::with_none()
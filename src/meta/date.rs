@@ -1,5 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
+use crate::flags::{DateField, DateFlag, DateGranularity, Flags, TimePrecision};
 use chrono_humanize::HumanTime;
 use std::fs::Metadata;
 use std::time::UNIX_EPOCH;
@@ -12,19 +12,74 @@ impl<'a> From<&'a Metadata> for Date {
     fn from(meta: &'a Metadata) -> Self {
         let modified_time = meta.modified().expect("failed to retrieve modified date");
 
-        let modified_time_since_epoch =
-            modified_time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Self::from_system_time(modified_time)
+    }
+}
+
+impl Default for Date {
+    /// The placeholder date used for entries whose metadata could not be read: the epoch.
+    fn default() -> Self {
+        Date(time::at(Timespec::new(0, 0)))
+    }
+}
+
+impl Date {
+    /// Get the creation date from the [Metadata], if the filesystem records one.
+    pub fn from_creation(meta: &Metadata) -> Option<Self> {
+        meta.created().ok().map(Self::from_system_time)
+    }
+
+    /// Get the access date from the [Metadata], if the filesystem records one.
+    pub fn from_access(meta: &Metadata) -> Option<Self> {
+        meta.accessed().ok().map(Self::from_system_time)
+    }
+
+    /// Get the status change date from the [Metadata], where the platform records one.
+    #[cfg(unix)]
+    pub fn from_change(meta: &Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        Some(Date(time::at(Timespec::new(
+            meta.ctime(),
+            meta.ctime_nsec() as i32,
+        ))))
+    }
+
+    /// Get the status change date from the [Metadata], where the platform records one.
+    #[cfg(not(unix))]
+    pub fn from_change(_: &Metadata) -> Option<Self> {
+        None
+    }
+
+    /// Get the date selected by `--date-field`, falling back to the modification date where
+    /// the filesystem does not record the requested one.
+    pub fn for_field(meta: &Metadata, field: DateField) -> Self {
+        let selected = match field {
+            DateField::Mod => None,
+            DateField::Access => Self::from_access(meta),
+            DateField::Change => Self::from_change(meta),
+            DateField::Birth => Self::from_creation(meta),
+        };
+
+        selected.unwrap_or_else(|| Self::from(meta))
+    }
+
+    fn from_system_time(system_time: std::time::SystemTime) -> Self {
+        let time_since_epoch = system_time.duration_since(UNIX_EPOCH).unwrap_or_default();
 
         let time = time::at(Timespec::new(
-            modified_time_since_epoch.as_secs() as i64,
-            modified_time_since_epoch.subsec_nanos() as i32,
+            time_since_epoch.as_secs() as i64,
+            time_since_epoch.subsec_nanos() as i32,
         ));
 
         Date(time)
     }
-}
 
-impl Date {
+    /// The date as whole seconds since the epoch, for machine readable output.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0.to_timespec().sec
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let now = time::now();
 
@@ -37,14 +92,96 @@ impl Date {
             elem = &Elem::Older;
         }
 
+        if flags.date == DateFlag::Combined {
+            // The relative part carries less information, so dim it next to the exact date.
+            let strings: &[ColoredString] = &[
+                colors.colorize(self.0.to_local().strftime("%F").unwrap().to_string(), elem),
+                colors.colorize(
+                    format!(" ({})", HumanTime::from(self.0 - time::now())),
+                    &Elem::NoAccess,
+                ),
+            ];
+
+            let res = ansi_term::ANSIStrings(strings).to_string();
+            return ColoredString::from(res);
+        }
+
         colors.colorize(self.date_string(&flags), elem)
     }
 
+    /// Render the elapsed time since this date in human terms, with the same age coloring as
+    /// [render](Date::render).
+    pub fn render_age(&self, colors: &Colors) -> ColoredString {
+        let now = time::now();
+
+        let elem = if self.0 > now - Duration::hours(1) {
+            &Elem::HourOld
+        } else if self.0 > now - Duration::days(1) {
+            &Elem::DayOld
+        } else {
+            &Elem::Older
+        };
+
+        colors.colorize(format!("{}", HumanTime::from(self.0 - now)), elem)
+    }
+
     pub fn date_string(&self, flags: &Flags) -> String {
+        let fraction = self.fraction_string(flags);
+
         match &flags.date {
-            DateFlag::Date => self.0.ctime().to_string(),
-            DateFlag::Relative => format!("{}", HumanTime::from(self.0 - time::now())),
-            DateFlag::Formatted(format) => self.0.to_local().strftime(&format).unwrap().to_string(),
+            DateFlag::Date => match fraction.is_empty() {
+                true => self.0.ctime().to_string(),
+                false => format!(
+                    "{}{}{}",
+                    self.0.strftime("%a %b %e %T").unwrap(),
+                    fraction,
+                    self.0.strftime(" %Y").unwrap()
+                ),
+            },
+            DateFlag::Relative => self.relative_string(flags),
+            DateFlag::Combined => format!(
+                "{} ({})",
+                self.0.to_local().strftime("%F").unwrap(),
+                HumanTime::from(self.0 - time::now())
+            ),
+            DateFlag::Formatted(format) => {
+                // Splice the fractional digits into the format behind the seconds, so styles
+                // like full-iso keep their field order.
+                let format = format.replace("%S", &format!("%S{}", fraction));
+                self.0.to_local().strftime(&format).unwrap().to_string()
+            }
+        }
+    }
+
+    /// The humanized distance to now, at the granularity the flags ask for: rough rounds to
+    /// the largest unit, precise spells out every component.
+    fn relative_string(&self, flags: &Flags) -> String {
+        use chrono_humanize::{Accuracy, Tense};
+
+        let duration = self.0 - time::now();
+        let human = HumanTime::from(duration);
+
+        match flags.date_granularity {
+            DateGranularity::Rough => format!("{}", human),
+            DateGranularity::Precise => {
+                let tense = if duration > Duration::zero() {
+                    Tense::Future
+                } else {
+                    Tense::Past
+                };
+                human.to_text_en(Accuracy::Precise, tense)
+            }
+        }
+    }
+
+    /// The fractional part of the seconds as dictated by the
+    /// [TimePrecision](crate::flags::TimePrecision) flag, including the leading dot. For the
+    /// default whole second precision this is empty.
+    fn fraction_string(&self, flags: &Flags) -> String {
+        match flags.time_precision {
+            TimePrecision::Second => String::new(),
+            TimePrecision::Millisecond => format!(".{:03}", self.0.tm_nsec / 1_000_000),
+            TimePrecision::Nanosecond => format!(".{:09}", self.0.tm_nsec),
         }
     }
 }
@@ -52,8 +189,8 @@ impl Date {
 #[cfg(test)]
 mod test {
     use super::Date;
-    use crate::color::{Colors, Theme};
-    use crate::flags::{DateFlag, Flags};
+    use crate::color::{Colors, Palette, Theme};
+    use crate::flags::{DateField, DateFlag, DateGranularity, Flags, TimePrecision};
     use ansi_term::Colour;
     use std::io;
     use std::path::Path;
@@ -107,7 +244,7 @@ mod test {
             .success();
         assert!(success, "failed to exec touch");
 
-        let colors = Colors::new(Theme::Default);
+        let colors = Colors::new(Theme::Default, Palette::Default);
         let date = Date::from(&file_path.metadata().unwrap());
         let flags = Flags::default();
 
@@ -131,7 +268,7 @@ mod test {
             .success();
         assert!(success, "failed to exec touch");
 
-        let colors = Colors::new(Theme::Default);
+        let colors = Colors::new(Theme::Default, Palette::Default);
         let date = Date::from(&file_path.metadata().unwrap());
         let flags = Flags::default();
 
@@ -155,7 +292,7 @@ mod test {
             .success();
         assert!(success, "failed to exec touch");
 
-        let colors = Colors::new(Theme::Default);
+        let colors = Colors::new(Theme::Default, Palette::Default);
         let date = Date::from(&file_path.metadata().unwrap());
         let flags = Flags::default();
 
@@ -179,7 +316,7 @@ mod test {
             .success();
         assert!(success, "failed to exec touch");
 
-        let colors = Colors::new(Theme::Default);
+        let colors = Colors::new(Theme::Default, Palette::Default);
         let date = Date::from(&file_path.metadata().unwrap());
 
         let mut flags = Flags::default();
@@ -193,6 +330,82 @@ mod test {
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_for_field_selects_date() {
+        let mut file_path = env::temp_dir();
+        file_path.push("test_for_field_selects_date.tmp");
+
+        let modification_date = (time::now() - time::Duration::days(2)).to_local();
+        let success = cross_platform_touch(&file_path, &modification_date)
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let access_date = (time::now() - time::Duration::days(1)).to_local();
+        let success = Command::new("touch")
+            .arg("-a")
+            .arg("-t")
+            .arg(access_date.strftime("%Y%m%d%H%M.%S").unwrap().to_string())
+            .arg(&file_path)
+            .status()
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let metadata = file_path.metadata().unwrap();
+
+        assert_eq!(
+            Date::from(&metadata),
+            Date::for_field(&metadata, DateField::Mod)
+        );
+        assert_eq!(
+            Date::from_access(&metadata).unwrap(),
+            Date::for_field(&metadata, DateField::Access)
+        );
+        assert_ne!(
+            Date::for_field(&metadata, DateField::Mod),
+            Date::for_field(&metadata, DateField::Access)
+        );
+        assert_eq!(
+            Date::from_change(&metadata).unwrap(),
+            Date::for_field(&metadata, DateField::Change)
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_relative_date_granularity() {
+        let date = Date(time::now() - time::Duration::days(2) - time::Duration::hours(3));
+
+        let mut flags = Flags::default();
+        flags.date = DateFlag::Relative;
+        assert_eq!("2 days ago", date.date_string(&flags));
+
+        // The precise spelling depends on sub-second drift between the two now() calls, so
+        // only the components which can not drift away are asserted.
+        flags.date_granularity = DateGranularity::Precise;
+        let precise = date.date_string(&flags);
+        assert!(precise.contains("2 days"), "was: {}", precise);
+        assert!(precise.contains("hours"), "was: {}", precise);
+        assert!(precise.ends_with("ago"), "was: {}", precise);
+    }
+
+    #[test]
+    fn test_fraction_string_precision() {
+        let date = Date(time::at(time::Timespec::new(0, 123_456_789)));
+
+        let mut flags = Flags::default();
+        assert_eq!("", date.fraction_string(&flags));
+
+        flags.time_precision = TimePrecision::Millisecond;
+        assert_eq!(".123", date.fraction_string(&flags));
+
+        flags.time_precision = TimePrecision::Nanosecond;
+        assert_eq!(".123456789", date.fraction_string(&flags));
+    }
+
     #[test]
     fn test_with_relative_date_now() {
         let mut file_path = env::temp_dir();
@@ -204,7 +417,7 @@ mod test {
             .success();
         assert_eq!(true, success, "failed to exec touch");
 
-        let colors = Colors::new(Theme::Default);
+        let colors = Colors::new(Theme::Default, Palette::Default);
         let date = Date::from(&file_path.metadata().unwrap());
 
         let mut flags = Flags::default();
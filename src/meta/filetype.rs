@@ -107,7 +107,7 @@ impl FileType {
 #[cfg(test)]
 mod test {
     use super::FileType;
-    use crate::color::{Colors, Theme};
+    use crate::color::{Colors, Palette, Theme};
     use crate::meta::Meta;
     #[cfg(unix)]
     use crate::meta::Permissions;
@@ -132,7 +132,7 @@ mod test {
         File::create(&file_path).expect("failed to create file");
         let meta = file_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(184).paint("."), file_type.render(&colors));
@@ -145,7 +145,7 @@ mod test {
             .expect("failed to get tempdir path");
         let metadata = tmp_dir.path().metadata().expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&metadata, None, &meta.permissions);
 
         assert_eq!(Colour::Fixed(33).paint("d"), file_type.render(&colors));
@@ -167,7 +167,7 @@ mod test {
             .symlink_metadata()
             .expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, Some(&meta), &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(44).paint("l"), file_type.render(&colors));
@@ -189,7 +189,7 @@ mod test {
             .symlink_metadata()
             .expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, Some(&meta), &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(44).paint("l"), file_type.render(&colors));
@@ -210,7 +210,7 @@ mod test {
         assert_eq!(true, success, "failed to exec mkfifo");
         let meta = pipe_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(44).paint("|"), file_type.render(&colors));
@@ -235,7 +235,7 @@ mod test {
         assert_eq!(true, success, "failed to exec mknod");
         let meta = char_device_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(44).paint("c"), file_type.render(&colors));
@@ -251,7 +251,7 @@ mod test {
         UnixListener::bind(&socket_path).expect("failed to create the socket");
         let meta = socket_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(Theme::NoLscolors);
+        let colors = Colors::new(Theme::NoLscolors, Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
 
         assert_eq!(Colour::Fixed(44).paint("s"), file_type.render(&colors));
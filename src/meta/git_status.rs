@@ -0,0 +1,160 @@
+use crate::color::{ColoredString, Colors, Elem};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The git status of an entry, derived from the porcelain output of `git status` for the
+/// containing repository.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum GitStatus {
+    /// The entry is tracked and unchanged, or outside any repository.
+    Unmodified,
+    /// The entry has changes in the worktree which are not staged yet.
+    Modified,
+    /// The entry has changes staged in the index.
+    Staged,
+    /// The entry is not tracked by the repository.
+    Untracked,
+    /// The entry is matched by the repository's ignore rules.
+    Ignored,
+}
+
+impl GitStatus {
+    /// Get the git status of the given path. The porcelain output is gathered once per
+    /// repository and cached, so large listings cost one `git` invocation per repository
+    /// instead of one per entry.
+    pub fn from_path(path: &Path) -> Self {
+        thread_local! {
+            static ROOT_CACHE: RefCell<HashMap<PathBuf, Option<PathBuf>>> =
+                RefCell::new(HashMap::new());
+            static STATUS_CACHE: RefCell<HashMap<PathBuf, HashMap<PathBuf, GitStatus>>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let root = ROOT_CACHE.with(|cache| {
+            cache
+                .borrow_mut()
+                .entry(parent.clone())
+                .or_insert_with(|| repository_root(&parent))
+                .clone()
+        });
+
+        let root = match root {
+            Some(root) => root,
+            None => return Self::Unmodified,
+        };
+
+        STATUS_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let statuses = cache
+                .entry(root.clone())
+                .or_insert_with(|| repository_statuses(&root));
+
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            if let Some(status) = statuses.get(&path) {
+                return *status;
+            }
+
+            // Untracked and ignored directories are reported as a single entry, so entries
+            // below them inherit the status of the closest reported ancestor.
+            for ancestor in path.ancestors().skip(1) {
+                if !ancestor.starts_with(&root) {
+                    break;
+                }
+
+                if let Some(status) = statuses.get(ancestor) {
+                    return *status;
+                }
+            }
+
+            Self::Unmodified
+        })
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString {
+        let (chr, elem) = match self {
+            Self::Unmodified => ("-", &Elem::NoAccess),
+            Self::Modified => ("M", &Elem::GitModified),
+            Self::Staged => ("S", &Elem::GitStaged),
+            Self::Untracked => ("?", &Elem::GitUntracked),
+            Self::Ignored => ("I", &Elem::GitIgnored),
+        };
+
+        colors.colorize(String::from(chr), elem)
+    }
+}
+
+/// The toplevel of the repository containing the given directory, if any.
+fn repository_root(directory: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(&["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let root = String::from_utf8(output.stdout).ok()?;
+    Some(PathBuf::from(root.trim_end_matches('\n')))
+}
+
+/// The status of every changed, untracked or ignored path in the repository, keyed by
+/// absolute path. Paths not in the map are clean.
+fn repository_statuses(root: &Path) -> HashMap<PathBuf, GitStatus> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(&["status", "--porcelain", "-z", "--ignored"])
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return HashMap::new(),
+    };
+
+    let mut statuses = HashMap::new();
+    let records = String::from_utf8_lossy(&output);
+    let mut skip_next = false;
+    for record in records.split('\0') {
+        // Renames and copies are followed by a second record holding the original path.
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+
+        if record.len() < 4 {
+            continue;
+        }
+
+        let (prefix, entry_path) = record.split_at(3);
+        let mut prefix = prefix.chars();
+        let index = prefix.next().unwrap_or(' ');
+        let worktree = prefix.next().unwrap_or(' ');
+
+        skip_next = index == 'R' || index == 'C';
+
+        let status = match (index, worktree) {
+            ('?', _) => GitStatus::Untracked,
+            ('!', _) => GitStatus::Ignored,
+            // A worktree change wins over a staged one, since it is the one not yet recorded
+            // anywhere.
+            (_, 'M') | (_, 'D') | (_, 'T') => GitStatus::Modified,
+            (' ', _) => GitStatus::Unmodified,
+            _ => GitStatus::Staged,
+        };
+
+        statuses.insert(root.join(entry_path.trim_end_matches('/')), status);
+    }
+
+    statuses
+}
@@ -1,7 +1,7 @@
 use crate::color::{ColoredString, Colors, Elem};
 use std::fs::Metadata;
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
 pub struct INode {
     index: Option<u64>,
 }
@@ -23,6 +23,11 @@ impl<'a> From<&'a Metadata> for INode {
 }
 
 impl INode {
+    /// The raw inode number, if the platform records one.
+    pub fn number(&self) -> Option<u64> {
+        self.index
+    }
+
     pub fn render(&self, colors: &Colors) -> ColoredString {
         match self.index {
             Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
@@ -54,9 +59,6 @@ mod tests {
 
         let inode = INode::from(&file_path.metadata().unwrap());
 
-        #[cfg(unix)]
         assert!(inode.index.is_some());
-        #[cfg(windows)]
-        assert!(inode.index.is_none());
     }
 }
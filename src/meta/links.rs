@@ -0,0 +1,78 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use ansi_term::ANSIStrings;
+use std::fs::Metadata;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Links {
+    nlink: Option<u64>,
+}
+
+impl<'a> From<&'a Metadata> for Links {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        let nlink = meta.nlink();
+
+        Self { nlink: Some(nlink) }
+    }
+
+    #[cfg(windows)]
+    fn from(_: &Metadata) -> Self {
+        Self { nlink: None }
+    }
+}
+
+impl Links {
+    pub fn render(&self, colors: &Colors, flags: &Flags, val_alignment: usize) -> ColoredString {
+        let content = match self.nlink {
+            Some(i) => colors.colorize(i.to_string(), &Elem::Links { valid: true }),
+            None => colors.colorize(String::from("-"), &Elem::Links { valid: false }),
+        };
+
+        let mut left_pad = String::new();
+        for _ in 0..val_alignment.saturating_sub(self.value_string().len()) {
+            left_pad.push(flags.padding.fill());
+        }
+
+        let strings: &[ColoredString] = &[ColoredString::from(left_pad), content];
+        let res = ANSIStrings(strings).to_string();
+        ColoredString::from(res)
+    }
+
+    /// The unstyled column content, used for the alignment of the hard link counts.
+    pub fn value_string(&self) -> String {
+        match self.nlink {
+            Some(i) => i.to_string(),
+            None => String::from("-"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::Links;
+    use std::env;
+    use std::io;
+    use std::path::Path;
+    use std::process::{Command, ExitStatus};
+
+    fn cross_platform_touch(path: &Path) -> io::Result<ExitStatus> {
+        Command::new("touch").arg(&path).status()
+    }
+
+    #[test]
+    fn test_hardlink_count_no_zero() {
+        let mut file_path = env::temp_dir();
+        file_path.push("nlink.tmp");
+
+        let success = cross_platform_touch(&file_path).unwrap().success();
+        assert!(success, "failed to exec touch");
+
+        let links = Links::from(&file_path.metadata().unwrap());
+
+        assert!(links.nlink.unwrap() >= 1);
+    }
+}
@@ -1,47 +1,92 @@
 mod date;
 mod filetype;
+mod git_status;
 mod indicator;
 mod inode;
+mod links;
 pub mod name;
 mod owner;
 mod permissions;
 mod size;
 mod symlink;
+mod xattr;
 
 #[cfg(windows)]
 mod windows_utils;
 
 pub use self::date::Date;
 pub use self::filetype::FileType;
+pub use self::git_status::GitStatus;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
+pub use self::links::Links;
 pub use self::name::Name;
 pub use self::owner::Owner;
 pub use self::permissions::Permissions;
 pub use self::size::Size;
 pub use self::symlink::SymLink;
+pub use self::xattr::XAttrs;
 pub use crate::icon::Icons;
 
+use crate::color::{ColoredString, Colors, Elem};
 use crate::flags::{Display, Flags, Layout};
 use crate::print_error;
 
+use std::collections::{HashMap, HashSet};
 use std::fs::read_link;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::io::{Error, ErrorKind};
 use std::path::{Component, Path, PathBuf};
 
+/// Check whether the entry at `path` carries the BSD `UF_HIDDEN` file flag.
+#[cfg(target_os = "macos")]
+fn has_uf_hidden_flag(path: &Path) -> bool {
+    use std::os::macos::fs::MetadataExt;
+
+    // The `UF_HIDDEN` flag from `sys/stat.h`, telling the GUI to hide the entry.
+    const UF_HIDDEN: u32 = 0x0000_8000;
+
+    match path.symlink_metadata() {
+        Ok(metadata) => metadata.st_flags() & UF_HIDDEN != 0,
+        Err(_) => false,
+    }
+}
+
+/// Check whether the entry at `path` carries the Windows Hidden or System file attribute.
+#[cfg(windows)]
+fn has_hidden_attribute(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    use winapi::um::winnt::{FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_SYSTEM};
+
+    match path.symlink_metadata() {
+        Ok(metadata) => {
+            metadata.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0
+        }
+        Err(_) => false,
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Meta {
     pub name: Name,
     pub path: PathBuf,
     pub permissions: Permissions,
     pub date: Date,
+    pub created: Option<Date>,
+    pub accessed: Option<Date>,
     pub owner: Owner,
     pub file_type: FileType,
     pub size: Size,
     pub symlink: SymLink,
     pub indicator: Indicator,
     pub inode: INode,
+    pub links: Links,
+    pub git_status: Option<GitStatus>,
+    pub xattrs: XAttrs,
     pub content: Option<Vec<Meta>>,
+    /// The error which prevented the entry's metadata from being read, if any. Such entries
+    /// are rendered with placeholder blocks instead of being dropped from the listing.
+    pub error: Option<String>,
 }
 
 impl Meta {
@@ -58,17 +103,37 @@ impl Meta {
             return Ok(None);
         }
 
+        let mut depth = depth;
         match self.file_type {
             FileType::Directory { .. } => (),
             FileType::SymLink { is_dir: true } => {
                 if flags.layout == Layout::OneLine {
                     return Ok(None);
                 }
+
+                // A link farm can blow up the tree layout, so links can be capped to expand
+                // only a few levels deep. The cap is renewed at every link passed through.
+                if let Some(link_depth) = flags.follow_links_at_depth.0 {
+                    if link_depth < depth {
+                        depth = link_depth;
+                        if depth == 0 {
+                            return Ok(None);
+                        }
+                    }
+                }
             }
             _ => return Ok(None),
         }
 
-        let entries = match self.path.read_dir() {
+        // Deep trees can exceed the classic Win32 path limit; reading through the
+        // extended-length form keeps the recursion going, at the cost of the verbatim prefix
+        // showing up in the paths below this depth.
+        #[cfg(windows)]
+        let read_path = windows_utils::extended_length(&self.path);
+        #[cfg(not(windows))]
+        let read_path = &self.path;
+
+        let entries = match read_path.read_dir() {
             Ok(entries) => entries,
             Err(err) => {
                 print_error!("lsd: {}: {}\n", self.path.display(), err);
@@ -76,6 +141,21 @@ impl Meta {
             }
         };
 
+        // Local ignore files only apply to the directory holding them, so the globs are
+        // rebuilt for every level of the recursion.
+        let local_ignore = if flags.git_ignore.0 {
+            let ignore_path = self.path.join(".ignore");
+            if ignore_path.is_file() {
+                Some(crate::flags::ignore_file::from_patterns_file(
+                    &ignore_path.to_string_lossy(),
+                ))
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
         let mut content: Vec<Meta> = Vec::new();
 
         if let Display::All = flags.display {
@@ -91,6 +171,7 @@ impl Meta {
             content.push(parent_meta);
         }
 
+        let mut paths: Vec<PathBuf> = Vec::new();
         for entry in entries {
             let path = entry?.path();
 
@@ -98,7 +179,17 @@ impl Meta {
                 .file_name()
                 .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
 
-            if flags.ignore_globs.0.is_match(&name) {
+            if flags.ignore_globs.0.is_match(&name) || flags.ignore_file.0.is_match(&name) {
+                continue;
+            }
+
+            if let Some(globs) = &local_ignore {
+                if globs.is_match(&name) {
+                    continue;
+                }
+            }
+
+            if flags.git_ignore.0 && GitStatus::from_path(&path) == GitStatus::Ignored {
                 continue;
             }
 
@@ -106,15 +197,38 @@ impl Meta {
                 if name.to_string_lossy().starts_with('.') {
                     continue;
                 }
+
+                // Finder treats entries carrying the UF_HIDDEN flag like dot files, so
+                // follow its notion of hidden on macOS.
+                #[cfg(target_os = "macos")]
+                {
+                    if has_uf_hidden_flag(&path) {
+                        continue;
+                    }
+                }
+
+                // Explorer hides entries carrying the Hidden or System attribute, so
+                // follow its notion of hidden on Windows unless asked otherwise.
+                #[cfg(windows)]
+                {
+                    if flags.windows_attributes.0 && has_hidden_attribute(&path) {
+                        continue;
+                    }
+                }
             }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0) {
-                Ok(res) => res,
-                Err(err) => {
-                    print_error!("lsd: {}: {}\n", path.display(), err);
-                    continue;
+            paths.push(path);
+        }
+
+        for mut entry_meta in Self::metas_from_paths(paths, flags) {
+            let path = entry_meta.path.clone();
+
+            #[cfg(unix)]
+            {
+                if flags.foreign_owner.0 && entry_meta.owner.uid() != self.owner.uid() {
+                    entry_meta.owner.mark_foreign();
                 }
-            };
+            }
 
             match entry_meta.recurse_into(depth - 1, &flags) {
                 Ok(content) => entry_meta.content = content,
@@ -130,6 +244,462 @@ impl Meta {
         Ok(Some(content))
     }
 
+    /// Gather the metadata for the entries of one directory, spreading the syscalls over
+    /// several threads when the directory is large. The stat calls are cheap enough locally
+    /// that the thread handling only pays off on big directories, and above all on slow
+    /// network filesystems, where each call is a round trip.
+    fn metas_from_paths(paths: Vec<PathBuf>, flags: &Flags) -> Vec<Meta> {
+        /// The number of entries below which the metadata is gathered on the calling thread.
+        const PARALLEL_LISTING: usize = 1024;
+
+        let threads = std::thread::available_parallelism()
+            .map(std::num::NonZeroUsize::get)
+            .unwrap_or(1)
+            .min(8);
+
+        if paths.len() < PARALLEL_LISTING || threads < 2 {
+            return Self::metas_from_path_chunk(&paths, flags.dereference.0);
+        }
+
+        let chunk_size = (paths.len() + threads - 1) / threads;
+        let dereference = flags.dereference.0;
+
+        let mut handles = Vec::new();
+        for chunk in paths.chunks(chunk_size).skip(1) {
+            let chunk = chunk.to_vec();
+            handles.push(std::thread::spawn(move || {
+                Self::metas_from_path_chunk(&chunk, dereference)
+            }));
+        }
+
+        let mut metas = Self::metas_from_path_chunk(&paths[..chunk_size], dereference);
+        for handle in handles {
+            if let Ok(chunk) = handle.join() {
+                metas.extend(chunk);
+            }
+        }
+
+        metas
+    }
+
+    /// Get the [Meta] for each of the given paths in order. An entry which vanished or denies
+    /// access since the directory was read still gets one, with placeholders for everything
+    /// that could not be read.
+    fn metas_from_path_chunk(paths: &[PathBuf], dereference: bool) -> Vec<Meta> {
+        paths
+            .iter()
+            .map(|path| match Self::from_path(path, dereference) {
+                Ok(meta) => meta,
+                Err(err) => Self::from_failed_path(path, &err),
+            })
+            .collect()
+    }
+
+    /// Render the effective access of the [CheckAccess](crate::flags::CheckAccess) target to
+    /// this entry, derived from the entry's ownership and permission bits.
+    #[cfg(unix)]
+    pub fn render_access(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let target = match &flags.check_access.0 {
+            Some(target) => target,
+            None => return colors.colorize(String::from("???"), &Elem::NoAccess),
+        };
+
+        let permissions = &self.permissions;
+        let (read, write, execute) = if target.uid == Some(self.owner.uid()) {
+            (
+                permissions.user_read,
+                permissions.user_write,
+                permissions.user_execute,
+            )
+        } else if target.gids.contains(&self.owner.gid()) {
+            (
+                permissions.group_read,
+                permissions.group_write,
+                permissions.group_execute,
+            )
+        } else if target.uid.is_some() || !target.gids.is_empty() {
+            (
+                permissions.other_read,
+                permissions.other_write,
+                permissions.other_execute,
+            )
+        } else {
+            // The name could not be resolved to any user or group.
+            return colors.colorize(String::from("???"), &Elem::NoAccess);
+        };
+
+        let bit = |bit: bool, chr: &'static str, elem: &Elem| {
+            if bit {
+                colors.colorize(String::from(chr), elem)
+            } else {
+                colors.colorize(String::from("-"), &Elem::NoAccess)
+            }
+        };
+
+        let strings: &[ColoredString] = &[
+            bit(read, "r", &Elem::Read),
+            bit(write, "w", &Elem::Write),
+            bit(execute, "x", &Elem::Exec),
+        ];
+
+        let res = ansi_term::ANSIStrings(strings).to_string();
+        ColoredString::from(res)
+    }
+
+    /// Evaluating the access of an arbitrary account against a DACL is not implemented yet, so
+    /// this renders a placeholder on non-Unix platforms.
+    #[cfg(not(unix))]
+    pub fn render_access(&self, colors: &Colors, _flags: &Flags) -> ColoredString {
+        colors.colorize(String::from("???"), &Elem::NoAccess)
+    }
+
+    /// Render a lock badge when the entry can not be modified, either because it carries the
+    /// immutable attribute or because its filesystem is mounted read-only. Platforms without
+    /// either probe simply never report a lock.
+    pub fn render_lock_badge(&self, colors: &Colors) -> Option<ColoredString> {
+        if self.is_immutable() || self.on_readonly_mount() {
+            Some(colors.colorize(String::from(" \u{1F512}"), &Elem::Locked))
+        } else {
+            None
+        }
+    }
+
+    /// Check the immutable attribute through statx, which reports it without opening the
+    /// file. Filesystems which do not support attributes simply leave the mask empty.
+    #[cfg(target_os = "linux")]
+    fn is_immutable(&self) -> bool {
+        use std::os::unix::ffi::OsStrExt;
+
+        const STATX_ATTR_IMMUTABLE: u64 = 0x10;
+
+        let path = match std::ffi::CString::new(self.path.as_os_str().as_bytes()) {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
+
+        let mut stats: libc::statx = unsafe { std::mem::zeroed() };
+        let result = unsafe {
+            libc::statx(
+                libc::AT_FDCWD,
+                path.as_ptr(),
+                libc::AT_SYMLINK_NOFOLLOW,
+                libc::STATX_BASIC_STATS,
+                &mut stats,
+            )
+        };
+
+        result == 0
+            && stats.stx_attributes_mask & STATX_ATTR_IMMUTABLE != 0
+            && stats.stx_attributes & STATX_ATTR_IMMUTABLE != 0
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_immutable(&self) -> bool {
+        false
+    }
+
+    /// Whether the filesystem holding this entry is mounted read-only. The statvfs result is
+    /// cached per directory, so large listings only pay for it once.
+    #[cfg(unix)]
+    fn on_readonly_mount(&self) -> bool {
+        use std::os::unix::ffi::OsStrExt;
+
+        thread_local! {
+            static READONLY_CACHE: std::cell::RefCell<HashMap<PathBuf, bool>> =
+                std::cell::RefCell::new(HashMap::new());
+        }
+
+        let parent = self.path.parent().unwrap_or(&self.path).to_path_buf();
+        READONLY_CACHE.with(|cache| {
+            if let Some(known) = cache.borrow().get(&parent) {
+                return *known;
+            }
+
+            let result = match std::ffi::CString::new(parent.as_os_str().as_bytes()) {
+                Ok(path) => {
+                    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+                    let status = unsafe { libc::statvfs(path.as_ptr(), &mut stats) };
+                    status == 0 && stats.f_flag & libc::ST_RDONLY != 0
+                }
+                Err(_) => false,
+            };
+
+            cache.borrow_mut().insert(parent, result);
+            result
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn on_readonly_mount(&self) -> bool {
+        false
+    }
+
+    /// Summarize the immediate children of a directory by file extension, most frequent
+    /// first. This stays a single `read_dir` deep, so it is cheap enough to run per entry.
+    pub fn render_extension_stats(&self, colors: &Colors) -> Option<ColoredString> {
+        if !matches!(self.file_type, FileType::Directory { .. }) {
+            return None;
+        }
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for entry in std::fs::read_dir(&self.path).ok()?.flatten() {
+            if entry.file_type().map(|kind| kind.is_dir()).unwrap_or(true) {
+                continue;
+            }
+
+            if let Some(extension) = entry.path().extension() {
+                *counts
+                    .entry(format!(".{}", extension.to_string_lossy()))
+                    .or_insert(0) += 1;
+            }
+        }
+
+        if counts.is_empty() {
+            return None;
+        }
+
+        let mut stats: Vec<(String, usize)> = counts.into_iter().collect();
+        stats.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+
+        let stats: Vec<String> = stats
+            .iter()
+            .map(|(extension, count)| format!("{} {}", count, extension))
+            .collect();
+
+        Some(colors.colorize(format!(" [{}]", stats.join(", ")), &Elem::Dir { uid: false }))
+    }
+
+    /// Render the peer information of a socket or FIFO: whether something is bound to and
+    /// listening on the socket, and which processes hold the FIFO open. This is the kind of
+    /// information one is after when debugging IPC directories like `/run`.
+    #[cfg(target_os = "linux")]
+    pub fn render_peers(&self, colors: &Colors) -> Option<ColoredString> {
+        let (description, elem) = match self.file_type {
+            FileType::Socket => (self.socket_peers()?, Elem::Socket),
+            FileType::Pipe => (self.fifo_peers()?, Elem::Pipe),
+            _ => return None,
+        };
+
+        Some(colors.colorize(format!(" ({})", description), &elem))
+    }
+
+    /// Render a dimmed explanation of the setgid and sticky bits of a directory, for readers
+    /// who do not have the semantics of the mode string memorized.
+    pub fn render_bit_hints(&self, colors: &Colors) -> Option<ColoredString> {
+        if !matches!(self.file_type, FileType::Directory { .. }) {
+            return None;
+        }
+
+        let mut hints = Vec::new();
+        if self.permissions.setgid {
+            hints.push("new files inherit group");
+        }
+        if self.permissions.sticky {
+            hints.push("only owners can delete");
+        }
+
+        if hints.is_empty() {
+            None
+        } else {
+            Some(colors.colorize(format!(" ({})", hints.join(", ")), &Elem::NoAccess))
+        }
+    }
+
+    /// Peer information is only available through procfs, so this renders nothing on other
+    /// platforms.
+    #[cfg(not(target_os = "linux"))]
+    pub fn render_peers(&self, _colors: &Colors) -> Option<ColoredString> {
+        None
+    }
+
+    /// Describe the state of the socket bound to this path, by cross-referencing the kernel's
+    /// socket table in `/proc/net/unix`.
+    #[cfg(target_os = "linux")]
+    fn socket_peers(&self) -> Option<String> {
+        // The __SO_ACCEPTCON flag, marking a socket which called listen().
+        const ACCEPTING: &str = "00010000";
+
+        let path = self.path.canonicalize().ok()?;
+        let path = path.to_str()?;
+        let table = std::fs::read_to_string("/proc/net/unix").ok()?;
+
+        let mut listening = false;
+        let mut connected = 0;
+        for line in table.lines().skip(1) {
+            // The columns are: Num, RefCount, Protocol, Flags, Type, St, Inode and Path, where
+            // the path is missing for unnamed sockets.
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.len() < 8 || columns[7] != path {
+                continue;
+            }
+
+            if columns[3] == ACCEPTING {
+                listening = true;
+            } else if columns[5] == "03" {
+                // SS_CONNECTED
+                connected += 1;
+            }
+        }
+
+        Some(match (listening, connected) {
+            (true, 0) => "listening".to_string(),
+            (true, n) => format!("listening, {} connected", n),
+            (false, 0) => "no listener".to_string(),
+            (false, n) => format!("{} connected", n),
+        })
+    }
+
+    /// Count the processes holding this FIFO open for reading and for writing, by walking the
+    /// file descriptor tables under `/proc`. Descriptors of other users are not readable, so
+    /// the counts only cover processes visible to the invoking user.
+    #[cfg(target_os = "linux")]
+    fn fifo_peers(&self) -> Option<String> {
+        let path = self.path.canonicalize().ok()?;
+
+        let mut readers = 0;
+        let mut writers = 0;
+        for process in Path::new("/proc").read_dir().ok()?.flatten() {
+            if !process
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|chr| chr.is_ascii_digit())
+            {
+                continue;
+            }
+
+            let descriptors = match process.path().join("fd").read_dir() {
+                Ok(descriptors) => descriptors,
+                Err(_) => continue,
+            };
+
+            for descriptor in descriptors.flatten() {
+                if read_link(descriptor.path()).ok().as_deref() != Some(&path) {
+                    continue;
+                }
+
+                let info = process.path().join("fdinfo").join(descriptor.file_name());
+                let info = match std::fs::read_to_string(info) {
+                    Ok(info) => info,
+                    Err(_) => continue,
+                };
+
+                let flags = info
+                    .lines()
+                    .find_map(|line| line.strip_prefix("flags:"))
+                    .and_then(|flags| u32::from_str_radix(flags.trim(), 8).ok());
+
+                // The lower two bits of the open flags hold the access mode.
+                match flags.map(|flags| flags & 0b11) {
+                    Some(0) => readers += 1,        // O_RDONLY
+                    Some(1) => writers += 1,        // O_WRONLY
+                    Some(2) => {
+                        // O_RDWR
+                        readers += 1;
+                        writers += 1;
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Some(format!("{} readers, {} writers", readers, writers))
+    }
+
+    /// Render the name of the systemd unit holding this entry open, if any. This helps tracing
+    /// the ownership of runtime files in directories like `/run` or `/tmp` back to a service.
+    #[cfg(target_os = "linux")]
+    pub fn render_unit(&self, colors: &Colors) -> Option<ColoredString> {
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            static UNIT_TABLE: RefCell<Option<HashMap<PathBuf, String>>> = RefCell::new(None);
+        }
+
+        let path = self.path.canonicalize().ok()?;
+        let unit = UNIT_TABLE.with(|table| {
+            let mut table = table.borrow_mut();
+            table
+                .get_or_insert_with(Self::build_unit_table)
+                .get(&path)
+                .cloned()
+        })?;
+
+        Some(colors.colorize(format!(" [{}]", unit), &Elem::User))
+    }
+
+    /// Peer processes are only visible through procfs, so this renders nothing on other
+    /// platforms.
+    #[cfg(not(target_os = "linux"))]
+    pub fn render_unit(&self, _colors: &Colors) -> Option<ColoredString> {
+        None
+    }
+
+    /// Build a table mapping paths to the systemd unit of a process that holds them open, by
+    /// walking the file descriptor tables and working directories under `/proc` and
+    /// cross-referencing each process' cgroup.
+    #[cfg(target_os = "linux")]
+    fn build_unit_table() -> std::collections::HashMap<PathBuf, String> {
+        let mut table = std::collections::HashMap::new();
+
+        let processes = match Path::new("/proc").read_dir() {
+            Ok(processes) => processes,
+            Err(_) => return table,
+        };
+
+        for process in processes.flatten() {
+            if !process
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|chr| chr.is_ascii_digit())
+            {
+                continue;
+            }
+
+            let unit = match std::fs::read_to_string(process.path().join("cgroup")) {
+                Ok(cgroup) => match Self::unit_from_cgroup(&cgroup) {
+                    Some(unit) => unit,
+                    None => continue,
+                },
+                Err(_) => continue,
+            };
+
+            if let Ok(cwd) = read_link(process.path().join("cwd")) {
+                table.entry(cwd).or_insert_with(|| unit.clone());
+            }
+
+            let descriptors = match process.path().join("fd").read_dir() {
+                Ok(descriptors) => descriptors,
+                Err(_) => continue,
+            };
+
+            for descriptor in descriptors.flatten() {
+                if let Ok(target) = read_link(descriptor.path()) {
+                    table.entry(target).or_insert_with(|| unit.clone());
+                }
+            }
+        }
+
+        table
+    }
+
+    /// Extract the name of the systemd unit a process belongs to from the contents of its
+    /// `/proc/[pid]/cgroup` file.
+    #[cfg(target_os = "linux")]
+    fn unit_from_cgroup(cgroup: &str) -> Option<String> {
+        const UNIT_SUFFIXES: &[&str] = &[".service", ".socket", ".scope", ".mount", ".timer"];
+
+        cgroup
+            .lines()
+            // Prefer the unified hierarchy, then fall back to the systemd named one.
+            .filter(|line| line.starts_with("0::") || line.contains(":name=systemd:"))
+            .filter_map(|line| line.rsplit('/').next())
+            .find(|name| UNIT_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+            .map(str::to_string)
+    }
+
     pub fn calculate_total_size(&mut self) {
         if let FileType::Directory { .. } = self.file_type {
             if let Some(metas) = &mut self.content {
@@ -139,13 +709,86 @@ impl Meta {
                     size_accumulated += x.size.get_bytes();
                 }
                 self.size = Size::new(size_accumulated);
+            } else if read_link(&self.path).is_ok() {
+                // Under --dereference the entry is typed as a directory while its path is
+                // still a link, which the plain walker would count as the link itself.
+                let mut visited = HashSet::new();
+                self.size = Size::new(Meta::calculate_total_size_through_links(
+                    &self.path,
+                    &mut visited,
+                ));
             } else {
                 // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Size::new(Meta::calculate_total_file_size(&self.path));
+                self.size = Size::new(crate::index::total_size(
+                    &self.path,
+                    Meta::calculate_total_file_size,
+                ));
+            }
+        } else if let FileType::SymLink { is_dir: true } = self.file_type {
+            if let Some(metas) = &mut self.content {
+                // Under --dereference the link was already resolved and recursed into, so the
+                // content accumulates like for a plain directory.
+                let mut size_accumulated = self.size.get_bytes();
+                for x in &mut metas.iter_mut() {
+                    x.calculate_total_size();
+                    size_accumulated += x.size.get_bytes();
+                }
+                self.size = Size::new(size_accumulated);
+            } else {
+                // The length of the link itself says nothing useful, so report the cumulative
+                // size of the target instead. Further links are followed as well, but every
+                // directory is visited only once, so link loops terminate.
+                let mut visited = HashSet::new();
+                self.size = Size::new(Meta::calculate_total_size_through_links(
+                    &self.path,
+                    &mut visited,
+                ));
             }
         }
     }
 
+    fn calculate_total_size_through_links(path: &Path, visited: &mut HashSet<PathBuf>) -> u64 {
+        let metadata = match path.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                print_error!("lsd: {}: {}\n", path.display(), err);
+                return 0;
+            }
+        };
+
+        let file_type = metadata.file_type();
+        if file_type.is_file() {
+            return metadata.len();
+        } else if !file_type.is_dir() {
+            return 0;
+        }
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !visited.insert(canonical) {
+            return 0;
+        }
+
+        let mut size = metadata.len();
+        let entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(err) => {
+                print_error!("lsd: {}: {}\n", path.display(), err);
+                return size;
+            }
+        };
+        for entry in entries {
+            match entry {
+                Ok(entry) => {
+                    size += Meta::calculate_total_size_through_links(&entry.path(), visited);
+                }
+                Err(err) => {
+                    print_error!("lsd: {}: {}\n", path.display(), err);
+                }
+            }
+        }
+        size
+    }
+
     fn calculate_total_file_size(path: &PathBuf) -> u64 {
         let metadata = if read_link(&path).is_ok() {
             // If the file is a link, retrieve the metadata without following
@@ -191,11 +834,29 @@ impl Meta {
     }
 
     pub fn from_path(path: &Path, dereference: bool) -> Result<Self, std::io::Error> {
-        // If the file is a link then retrieve link metadata instead with target metadata (if present).
-        let (metadata, symlink_meta) = if read_link(path).is_ok() && !dereference {
-            (path.symlink_metadata()?, path.metadata().ok())
+        // Paths beyond the classic Win32 limit need the extended-length syntax for the
+        // metadata calls; the path stored in the result stays as given.
+        #[cfg(windows)]
+        let stat_path = &windows_utils::extended_length(path);
+        #[cfg(not(windows))]
+        let stat_path = path;
+
+        // Start from the link metadata, so regular files cost a single stat call. This matters
+        // on slow filesystems like the 9p mounts of Windows drives under WSL. If the file is a
+        // link then retrieve link metadata instead with target metadata (if present).
+        let metadata = stat_path.symlink_metadata()?;
+        let (metadata, symlink_meta) = if metadata.file_type().is_symlink() {
+            if dereference {
+                (stat_path.metadata()?, None)
+            } else if skip_remote_probes(path) {
+                // On a network mount the extra stat of the target crosses the wire again, so
+                // the degraded mode leaves the target unresolved.
+                (metadata, None)
+            } else {
+                (metadata, stat_path.metadata().ok())
+            }
         } else {
-            (path.metadata()?, None)
+            (metadata, None)
         };
 
         #[cfg(unix)]
@@ -204,24 +865,203 @@ impl Meta {
         let permissions = Permissions::from(&metadata);
 
         #[cfg(windows)]
-        let (owner, permissions) = windows_utils::get_file_data(&path)?;
+        let (owner, permissions) = {
+            use std::os::windows::fs::MetadataExt;
+            windows_utils::get_file_data(stat_path, metadata.file_attributes())?
+        };
 
         let file_type = FileType::new(&metadata, symlink_meta.as_ref(), &permissions);
         let name = Name::new(&path, file_type);
         let inode = INode::from(&metadata);
+        let links = Links::from(&metadata);
 
         Ok(Self {
             inode,
+            links,
             path: path.to_path_buf(),
             symlink: SymLink::from(path),
-            size: Size::from(&metadata),
-            date: Date::from(&metadata),
+            size: if size_from_blocks() {
+                Size::from_blocks(&metadata)
+            } else {
+                Size::from(&metadata)
+            },
+            date: Date::for_field(&metadata, date_field()),
+            created: Date::from_creation(&metadata),
+            accessed: Date::from_access(&metadata),
             indicator: Indicator::from(file_type),
             owner,
             permissions,
             name,
             file_type,
+            git_status: if git_status_enabled() {
+                Some(GitStatus::from_path(path))
+            } else {
+                None
+            },
+            xattrs: if xattrs_enabled() {
+                XAttrs::from_path(path)
+            } else {
+                XAttrs::default()
+            },
             content: None,
+            error: None,
         })
     }
+
+    /// Build a placeholder for an entry whose metadata could not be read, keeping the name
+    /// and recording the error so the row can carry a note instead of vanishing.
+    fn from_failed_path(path: &Path, err: &std::io::Error) -> Self {
+        let file_type = FileType::Special;
+
+        Self {
+            inode: INode::default(),
+            links: Links::default(),
+            path: path.to_path_buf(),
+            symlink: SymLink::default(),
+            size: Size::new(0),
+            date: Date::default(),
+            created: None,
+            accessed: None,
+            indicator: Indicator::from(file_type),
+            owner: Owner::new("?".into(), "?".into()),
+            permissions: Permissions::default(),
+            name: Name::new(path, file_type),
+            file_type,
+            git_status: None,
+            xattrs: XAttrs::default(),
+            content: None,
+            error: Some(err.to_string()),
+        }
+    }
+}
+
+/// Whether the expensive per-entry metadata probes should be skipped on network mounts. This
+/// is process wide state, toggled once from the flags before the traversal starts, so that
+/// [Meta::from_path] does not need an extra parameter through every call site.
+static FAST_NETWORK_FS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_fast_network_fs(enabled: bool) {
+    FAST_NETWORK_FS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the git status of each entry should be gathered. Process wide for the same reason
+/// as [FAST_NETWORK_FS]: [Meta::from_path] should not grow a parameter for every flag.
+static GIT_STATUS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_git_status(enabled: bool) {
+    GIT_STATUS.store(enabled, Ordering::Relaxed);
+}
+
+fn git_status_enabled() -> bool {
+    GIT_STATUS.load(Ordering::Relaxed)
+}
+
+/// Whether the extended attributes of each entry should be gathered, again process wide so
+/// [Meta::from_path] keeps its signature.
+static XATTRS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_xattrs(enabled: bool) {
+    XATTRS.store(enabled, Ordering::Relaxed);
+}
+
+fn xattrs_enabled() -> bool {
+    XATTRS.load(Ordering::Relaxed)
+}
+
+/// Whether the size of each entry reports the allocated blocks instead of the apparent
+/// size, as chosen by `--size-source`. Process wide so [Meta::from_path] keeps its
+/// signature.
+static SIZE_BLOCKS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_size_from_blocks(enabled: bool) {
+    SIZE_BLOCKS.store(enabled, Ordering::Relaxed);
+}
+
+fn size_from_blocks() -> bool {
+    SIZE_BLOCKS.load(Ordering::Relaxed)
+}
+
+/// Which timestamp the date of each entry holds, as chosen by `--date-field`. Stored as the
+/// discriminant of [DateField](crate::flags::DateField), process wide like the other probe
+/// toggles, so [Meta::from_path] keeps its signature.
+static DATE_FIELD: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_date_field(field: crate::flags::DateField) {
+    DATE_FIELD.store(field as usize, Ordering::Relaxed);
+}
+
+fn date_field() -> crate::flags::DateField {
+    use crate::flags::DateField;
+
+    match DATE_FIELD.load(Ordering::Relaxed) {
+        field if field == DateField::Access as usize => DateField::Access,
+        field if field == DateField::Change as usize => DateField::Change,
+        field if field == DateField::Birth as usize => DateField::Birth,
+        _ => DateField::Mod,
+    }
+}
+
+/// Whether the remote-filesystem probes should be skipped for this path. Only true when the
+/// fast mode is enabled and the containing filesystem looks like a network mount.
+#[cfg(target_os = "linux")]
+fn skip_remote_probes(path: &Path) -> bool {
+    if !FAST_NETWORK_FS.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    // One statfs call per directory is cheap next to a remote stat per entry, but caching it
+    // keeps the syscall count flat for large directories.
+    thread_local! {
+        static NETWORK_FS_CACHE: std::cell::RefCell<HashMap<PathBuf, bool>> =
+            std::cell::RefCell::new(HashMap::new());
+    }
+
+    let parent = path.parent().unwrap_or(path).to_path_buf();
+    NETWORK_FS_CACHE.with(|cache| {
+        if let Some(known) = cache.borrow().get(&parent) {
+            return *known;
+        }
+
+        let result = statfs_is_network(&parent);
+        cache.borrow_mut().insert(parent, result);
+        result
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn skip_remote_probes(_path: &Path) -> bool {
+    false
+}
+
+/// Check the filesystem magic of the given directory against the known network filesystems.
+#[cfg(target_os = "linux")]
+fn statfs_is_network(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    const NFS_SUPER_MAGIC: i64 = 0x6969;
+    const SMB_SUPER_MAGIC: i64 = 0x517b;
+    const SMB2_SUPER_MAGIC: i64 = 0xfe53_4d42;
+    const CIFS_SUPER_MAGIC: i64 = 0xff53_4d42;
+    // SSHFS and the other FUSE filesystems can not be told apart, so the fast mode treats
+    // them all as remote.
+    const FUSE_SUPER_MAGIC: i64 = 0x6573_5546;
+
+    let path = match std::ffi::CString::new(path.as_os_str().as_bytes()) {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
+
+    let mut stats: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stats) } != 0 {
+        return false;
+    }
+
+    matches!(
+        stats.f_type as i64,
+        NFS_SUPER_MAGIC
+            | SMB_SUPER_MAGIC
+            | SMB2_SUPER_MAGIC
+            | CIFS_SUPER_MAGIC
+            | FUSE_SUPER_MAGIC
+    )
 }
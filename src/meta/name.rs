@@ -15,6 +15,10 @@ pub enum DisplayOption<'a> {
 #[derive(Clone, Debug, Eq)]
 pub struct Name {
     pub name: String,
+    /// The lowercase variant of the name, cached for the case insensitive orderings. [None]
+    /// when the name already is its own lowercase variant, which is the common case and
+    /// avoids storing every name twice.
+    lowercase: Option<String>,
     path: PathBuf,
     extension: Option<String>,
     file_type: FileType,
@@ -31,14 +35,26 @@ impl Name {
             .extension()
             .map(|ext| ext.to_string_lossy().to_string());
 
+        let lowercase = if name.chars().any(char::is_uppercase) {
+            Some(name.to_lowercase())
+        } else {
+            None
+        };
+
         Self {
             name,
+            lowercase,
             path: PathBuf::from(path),
             extension,
             file_type,
         }
     }
 
+    /// The lowercase variant of the name, without recomputing it.
+    pub fn lowercase(&self) -> &str {
+        self.lowercase.as_deref().unwrap_or(&self.name)
+    }
+
     pub fn file_name(&self) -> &str {
         self.path
             .file_name()
@@ -102,7 +118,37 @@ impl Name {
         icons: &Icons,
         display_option: &DisplayOption,
     ) -> ColoredString {
-        let content = match display_option {
+        let elem = match self.file_type {
+            FileType::CharDevice => Elem::CharDevice,
+            FileType::Directory { uid } => Elem::Dir { uid },
+            FileType::SymLink { .. } => Elem::SymLink,
+            FileType::File { uid, exec } => Elem::File { uid, exec },
+            _ => Elem::File {
+                exec: false,
+                uid: false,
+            },
+        };
+
+        colors.colorize_using_path(self.content(icons, display_option), &self.path, &elem)
+    }
+
+    /// Render the name like [render](Name::render), but colored with the given [Elem] instead
+    /// of the one derived from the file type. Any `LS_COLORS` rule matching the path is
+    /// ignored, since the caller explicitly chose the color.
+    pub fn render_as(
+        &self,
+        colors: &Colors,
+        icons: &Icons,
+        display_option: &DisplayOption,
+        elem: &Elem,
+    ) -> ColoredString {
+        colors.colorize(self.content(icons, display_option), elem)
+    }
+
+    /// The rendered but uncolored name, with the icon prepended and the path shown the way
+    /// the [DisplayOption] asks for.
+    fn content(&self, icons: &Icons, display_option: &DisplayOption) -> String {
+        match display_option {
             DisplayOption::FileName => {
                 format!("{}{}", icons.get(self), self.escape(self.file_name()))
             }
@@ -116,7 +162,17 @@ impl Name {
                 icons.get(self),
                 self.escape(&self.path.to_string_lossy())
             ),
-        };
+        }
+    }
+
+    /// Render only the icon of the entry in a single cell, falling back to the classic
+    /// file-type letter when icons are disabled.
+    pub fn render_icon(&self, colors: &Colors, icons: &Icons) -> ColoredString {
+        let icon = icons.get(self);
+        if icon.is_empty() {
+            let res = self.file_type.render(colors).to_string();
+            return ColoredString::from(res);
+        }
 
         let elem = match self.file_type {
             FileType::CharDevice => Elem::CharDevice,
@@ -129,7 +185,7 @@ impl Name {
             },
         };
 
-        colors.colorize_using_path(content, &self.path, &elem)
+        colors.colorize_using_path(icon.trim_end().to_string(), &self.path, &elem)
     }
 
     pub fn extension(&self) -> Option<&str> {
@@ -143,21 +199,19 @@ impl Name {
 
 impl Ord for Name {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.name.to_lowercase().cmp(&other.name.to_lowercase())
+        self.lowercase().cmp(other.lowercase())
     }
 }
 
 impl PartialOrd for Name {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.name
-            .to_lowercase()
-            .partial_cmp(&other.name.to_lowercase())
+        Some(self.cmp(other))
     }
 }
 
 impl PartialEq for Name {
     fn eq(&self, other: &Self) -> bool {
-        self.name.eq_ignore_ascii_case(&other.name.to_lowercase())
+        self.lowercase() == other.lowercase()
     }
 }
 
@@ -192,7 +246,7 @@ mod test {
         File::create(&file_path).expect("failed to create file");
         let meta = file_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&file_path, file_type);
 
@@ -212,7 +266,7 @@ mod test {
         fs::create_dir(&dir_path).expect("failed to create the dir");
         let meta = Meta::from_path(&dir_path, false).unwrap();
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
 
         assert_eq!(
             Colour::Fixed(33).paint(" directory"),
@@ -238,7 +292,7 @@ mod test {
             .expect("failed to get metas");
         let target_meta = symlink_path.metadata().ok();
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, target_meta.as_ref(), &Permissions::from(&meta));
         let name = Name::new(&symlink_path, file_type);
 
@@ -266,7 +320,7 @@ mod test {
             .expect("failed to get metas");
         let target_meta = symlink_path.metadata().ok();
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, target_meta.as_ref(), &Permissions::from(&meta));
         let name = Name::new(&symlink_path, file_type);
 
@@ -292,7 +346,7 @@ mod test {
         assert_eq!(true, success, "failed to exec mkfifo");
         let meta = pipe_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&pipe_path, file_type);
 
@@ -312,7 +366,7 @@ mod test {
         File::create(&file_path).expect("failed to create file");
         let meta = Meta::from_path(&file_path, false).unwrap();
 
-        let colors = Colors::new(color::Theme::NoColor);
+        let colors = Colors::new(color::Theme::NoColor, color::Palette::Default);
 
         assert_eq!(
             "file.txt",
@@ -527,7 +581,7 @@ mod test {
         File::create(&file_path).expect("failed to create file");
         let meta = file_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&file_path, file_type);
 
@@ -540,7 +594,7 @@ mod test {
         File::create(&file_path).expect("failed to create file");
         let meta = file_path.metadata().expect("failed to get metas");
 
-        let colors = Colors::new(color::Theme::NoLscolors);
+        let colors = Colors::new(color::Theme::NoLscolors, color::Palette::Default);
         let file_type = FileType::new(&meta, None, &Permissions::from(&meta));
         let name = Name::new(&file_path, file_type);
 
@@ -1,4 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
 #[cfg(unix)]
 use std::fs::Metadata;
 
@@ -6,12 +7,88 @@ use std::fs::Metadata;
 pub struct Owner {
     user: String,
     group: String,
+    /// Whether the owner differs from the one of the containing directory, making the entry
+    /// stand out in shared project directories.
+    foreign: bool,
+    #[cfg(unix)]
+    uid: u32,
+    #[cfg(unix)]
+    gid: u32,
+    #[cfg(windows)]
+    user_sid: Option<String>,
+    #[cfg(windows)]
+    group_sid: Option<String>,
 }
 
 impl Owner {
-    #[cfg_attr(unix, allow(dead_code))]
     pub fn new(user: String, group: String) -> Self {
-        Self { user, group }
+        Self {
+            user,
+            group,
+            foreign: false,
+            #[cfg(unix)]
+            uid: 0,
+            #[cfg(unix)]
+            gid: 0,
+            #[cfg(windows)]
+            user_sid: None,
+            #[cfg(windows)]
+            group_sid: None,
+        }
+    }
+
+    /// The name of the user the entry belongs to.
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// The name of the group the entry belongs to.
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    /// The user id the entry belongs to.
+    #[cfg(unix)]
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    /// The group id the entry belongs to.
+    #[cfg(unix)]
+    pub fn gid(&self) -> u32 {
+        self.gid
+    }
+
+    /// Mark the owner as differing from the one of the containing directory.
+    pub fn mark_foreign(&mut self) {
+        self.foreign = true;
+    }
+
+    /// The [Elem] of the user block: the anomaly color when the owner is foreign.
+    fn user_elem(&self) -> &'static Elem {
+        if self.foreign {
+            &Elem::PermissionAnomaly
+        } else {
+            &Elem::User
+        }
+    }
+
+    /// Create an `Owner` that additionally remembers the raw SID strings, so they can be
+    /// displayed instead of the resolved account names on demand.
+    #[cfg(windows)]
+    pub fn with_sids(
+        user: String,
+        group: String,
+        user_sid: Option<String>,
+        group_sid: Option<String>,
+    ) -> Self {
+        Self {
+            user,
+            group,
+            foreign: false,
+            user_sid,
+            group_sid,
+        }
     }
 }
 
@@ -31,16 +108,54 @@ impl<'a> From<&'a Metadata> for Owner {
             None => meta.gid().to_string(),
         };
 
-        Self { user, group }
+        Self {
+            user,
+            group,
+            foreign: false,
+            uid: meta.uid(),
+            gid: meta.gid(),
+        }
     }
 }
 
 impl Owner {
-    pub fn render_user(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.user.clone(), &Elem::User)
+    #[cfg(windows)]
+    pub fn render_user(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let user = match &self.user_sid {
+            Some(sid) if flags.sids.0 => sid.clone(),
+            _ => self.user.clone(),
+        };
+
+        colors.colorize(user, self.user_elem())
+    }
+
+    #[cfg(unix)]
+    pub fn render_user(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let user = match flags.uid_map.map(self.uid) {
+            Some(mapped) => mapped.to_string(),
+            None => self.user.clone(),
+        };
+
+        colors.colorize(user, self.user_elem())
     }
 
-    pub fn render_group(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.group.clone(), &Elem::Group)
+    #[cfg(windows)]
+    pub fn render_group(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let group = match &self.group_sid {
+            Some(sid) if flags.sids.0 => sid.clone(),
+            _ => self.group.clone(),
+        };
+
+        colors.colorize(group, &Elem::Group)
+    }
+
+    #[cfg(unix)]
+    pub fn render_group(&self, colors: &Colors, flags: &Flags) -> ColoredString {
+        let group = match flags.uid_map.map(self.gid) {
+            Some(mapped) => mapped.to_string(),
+            None => self.group.clone(),
+        };
+
+        colors.colorize(group, &Elem::Group)
     }
 }
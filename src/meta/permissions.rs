@@ -1,8 +1,9 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Flags, PermissionFlag};
 use ansi_term::ANSIStrings;
 use std::fs::Metadata;
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
 pub struct Permissions {
     pub user_read: bool,
     pub user_write: bool,
@@ -19,6 +20,10 @@ pub struct Permissions {
     pub sticky: bool,
     pub setgid: bool,
     pub setuid: bool,
+
+    /// The raw Windows file attributes, used by the `attributes` permission rendering.
+    #[cfg(windows)]
+    pub attributes: u32,
 }
 
 impl<'a> From<&'a Metadata> for Permissions {
@@ -55,54 +60,191 @@ impl<'a> From<&'a Metadata> for Permissions {
 }
 
 impl Permissions {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
-        let bit = |bit, chr: &'static str, elem: &Elem| {
+    pub fn render(&self, colors: &Colors, flags: &Flags, is_dir: bool) -> ColoredString {
+        // An explicit baseline takes precedence over the one derived from the umask.
+        let (expected, strict) = if let Some((file_mode, dir_mode)) = flags.expect_mode.0 {
+            (Some(if is_dir { dir_mode } else { file_mode }), true)
+        } else if flags.umask_check.0 {
+            (Some(Self::expected_bits(is_dir)), false)
+        } else {
+            (None, false)
+        };
+
+        match flags.permission {
+            PermissionFlag::Rwx => self.render_rwx(colors, expected, strict, false),
+            PermissionFlag::Spaced => self.render_rwx(colors, expected, strict, true),
+            PermissionFlag::Octal => self.render_octal(colors),
+            PermissionFlag::Attributes => self.render_attributes(colors),
+            PermissionFlag::Disable => colors.colorize(String::from("-"), &Elem::NoAccess),
+        }
+    }
+
+    /// The permission bits a freshly created entry would get under the current umask.
+    #[cfg(unix)]
+    fn expected_bits(is_dir: bool) -> u32 {
+        (if is_dir { 0o777 } else { 0o666 }) & !process_umask()
+    }
+
+    #[cfg(not(unix))]
+    fn expected_bits(is_dir: bool) -> u32 {
+        if is_dir {
+            0o777
+        } else {
+            0o666
+        }
+    }
+
+    /// Render the permissions as an `rwx` string, highlighting the bits which deviate from
+    /// `expected`. A lenient check only flags the deviations a umask can explain: an extra
+    /// write bit or a missing execute bit. A strict check, used with an explicit baseline,
+    /// flags every deviating bit. With `spaced` the three triads are separated by spaces.
+    fn render_rwx(
+        &self,
+        colors: &Colors,
+        expected: Option<u32>,
+        strict: bool,
+        spaced: bool,
+    ) -> ColoredString {
+        let bit = |bit, chr: &'static str, elem: &Elem, mask: u32| {
+            let expectation = expected.map(|expected| expected & mask != 0);
+
             if bit {
+                // An extra write bit the umask should have stripped is the classic sign of a
+                // misconfigured creating process.
+                if expectation == Some(false) && (strict || chr == "w") {
+                    return colors.colorize(String::from(chr), &Elem::PermissionAnomaly);
+                }
                 colors.colorize(String::from(chr), elem)
             } else {
+                // A missing execute bit only counts on directories, since the expectation
+                // for plain files never contains one.
+                if expectation == Some(true) && (strict || chr == "x") {
+                    return colors.colorize(String::from("-"), &Elem::PermissionAnomaly);
+                }
                 colors.colorize(String::from("-"), &Elem::NoAccess)
             }
         };
 
-        let strings: &[ColoredString] = &[
+        let mut strings: Vec<ColoredString> = vec![
             // User permissions
-            bit(self.user_read, "r", &Elem::Read),
-            bit(self.user_write, "w", &Elem::Write),
+            bit(self.user_read, "r", &Elem::Read, 0o400),
+            bit(self.user_write, "w", &Elem::Write, 0o200),
             match (self.user_execute, self.setuid) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
+                (executable, false) => bit(executable, "x", &Elem::Exec, 0o100),
                 (false, true) => colors.colorize(String::from("S"), &Elem::ExecSticky),
                 (true, true) => colors.colorize(String::from("s"), &Elem::ExecSticky),
             },
             // Group permissions
-            bit(self.group_read, "r", &Elem::Read),
-            bit(self.group_write, "w", &Elem::Write),
+            bit(self.group_read, "r", &Elem::Read, 0o040),
+            bit(self.group_write, "w", &Elem::Write, 0o020),
             match (self.group_execute, self.setgid) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
+                (executable, false) => bit(executable, "x", &Elem::Exec, 0o010),
                 (false, true) => colors.colorize(String::from("S"), &Elem::ExecSticky),
                 (true, true) => colors.colorize(String::from("s"), &Elem::ExecSticky),
             },
             // Other permissions
-            bit(self.other_read, "r", &Elem::Read),
-            bit(self.other_write, "w", &Elem::Write),
+            bit(self.other_read, "r", &Elem::Read, 0o004),
+            bit(self.other_write, "w", &Elem::Write, 0o002),
             match (self.other_execute, self.sticky) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
+                (executable, false) => bit(executable, "x", &Elem::Exec, 0o001),
                 (false, true) => colors.colorize(String::from("T"), &Elem::ExecSticky),
                 (true, true) => colors.colorize(String::from("t"), &Elem::ExecSticky),
             },
         ];
 
+        if spaced {
+            strings.insert(6, ColoredString::from(" "));
+            strings.insert(3, ColoredString::from(" "));
+        }
+
+        let res = ANSIStrings(&strings).to_string();
+        ColoredString::from(res)
+    }
+
+    fn render_octal(&self, colors: &Colors) -> ColoredString {
+        colors.colorize(self.octal_string(), &Elem::Octal)
+    }
+
+    /// Get the four octal digits for the permissions, the first one holding the setuid, setgid
+    /// and sticky bits.
+    pub fn octal_string(&self) -> String {
+        let bits_to_octal =
+            |r: bool, w: bool, x: bool| (r as u16) * 4 + (w as u16) * 2 + (x as u16);
+
+        format!(
+            "{}{}{}{}",
+            bits_to_octal(self.setuid, self.setgid, self.sticky),
+            bits_to_octal(self.user_read, self.user_write, self.user_execute),
+            bits_to_octal(self.group_read, self.group_write, self.group_execute),
+            bits_to_octal(self.other_read, self.other_write, self.other_execute),
+        )
+    }
+
+    /// Render the Windows file attributes as an `arhs` string, keeping the effective rights
+    /// summary derived from the ACL for the read-only column.
+    #[cfg(windows)]
+    fn render_attributes(&self, colors: &Colors) -> ColoredString {
+        use winapi::um::winnt::{
+            FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY,
+            FILE_ATTRIBUTE_SYSTEM,
+        };
+
+        let bit = |bit: bool, chr: &'static str, elem: &Elem| {
+            if bit {
+                colors.colorize(String::from(chr), elem)
+            } else {
+                colors.colorize(String::from("-"), &Elem::NoAccess)
+            }
+        };
+        let has_attribute = |attribute| self.attributes & attribute != 0;
+
+        let strings: &[ColoredString] = &[
+            bit(has_attribute(FILE_ATTRIBUTE_ARCHIVE), "a", &Elem::Read),
+            bit(
+                has_attribute(FILE_ATTRIBUTE_READONLY) || !self.user_write,
+                "r",
+                &Elem::Write,
+            ),
+            bit(has_attribute(FILE_ATTRIBUTE_HIDDEN), "h", &Elem::Exec),
+            bit(has_attribute(FILE_ATTRIBUTE_SYSTEM), "s", &Elem::ExecSticky),
+        ];
+
         let res = ANSIStrings(strings).to_string();
         ColoredString::from(res)
     }
 
+    /// There are no Windows file attributes on other platforms, so fall back to the `rwx`
+    /// rendering there.
+    #[cfg(not(windows))]
+    fn render_attributes(&self, colors: &Colors) -> ColoredString {
+        self.render_rwx(colors, None, false, false)
+    }
+
     pub fn is_executable(&self) -> bool {
         self.user_execute || self.group_execute || self.other_execute
     }
 }
 
+/// The current umask of the process. It can only be read by replacing it, so it is set right
+/// back and the result cached for the rest of the run.
+#[cfg(unix)]
+fn process_umask() -> u32 {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static UMASK: AtomicU32 = AtomicU32::new(u32::MAX);
+
+    let cached = UMASK.load(Ordering::Relaxed);
+    if cached != u32::MAX {
+        return cached;
+    }
+
+    let umask = unsafe { libc::umask(0) };
+    unsafe { libc::umask(umask) };
+
+    UMASK.store(umask as u32, Ordering::Relaxed);
+    umask as u32
+}
+
 // More readable aliases for the permission bits exposed by libc.
 #[allow(trivial_numeric_casts)]
 #[cfg(unix)]
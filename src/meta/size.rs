@@ -1,5 +1,5 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{Flags, SizeFlag};
+use crate::flags::{Flags, SizeFlag, SizeUnitFlag};
 use ansi_term::ANSIStrings;
 use std::fs::Metadata;
 
@@ -30,6 +30,23 @@ impl Size {
         Self { bytes }
     }
 
+    /// The size as the allocated disk blocks, like `du`, making sparse files report the
+    /// space they really use. On platforms without a block count this falls back to the
+    /// apparent size.
+    #[cfg(unix)]
+    pub fn from_blocks(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            bytes: meta.blocks() * 512,
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_blocks(meta: &Metadata) -> Self {
+        Self::from(meta)
+    }
+
     pub fn get_bytes(&self) -> u64 {
         self.bytes
     }
@@ -38,14 +55,25 @@ impl Size {
         format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
     }
 
+    /// The factor between two neighbouring units: 1000 with decimal prefixes, 1024
+    /// otherwise.
+    fn unit_base(flags: &Flags) -> u64 {
+        match flags.size_unit {
+            SizeUnitFlag::Decimal => 1000,
+            _ => 1024,
+        }
+    }
+
     pub fn get_unit(&self, flags: &Flags) -> Unit {
-        if self.bytes < 1024 || flags.size == SizeFlag::Bytes {
+        let base = Self::unit_base(flags);
+
+        if self.bytes < base || flags.size == SizeFlag::Bytes {
             Unit::Byte
-        } else if self.bytes < 1024 * 1024 {
+        } else if self.bytes < base * base {
             Unit::Kilo
-        } else if self.bytes < 1024 * 1024 * 1024 {
+        } else if self.bytes < base * base * base {
             Unit::Mega
-        } else if self.bytes < 1024 * 1024 * 1024 * 1024 {
+        } else if self.bytes < base * base * base * base {
             Unit::Giga
         } else {
             Unit::Tera
@@ -53,12 +81,16 @@ impl Size {
     }
 
     pub fn render(&self, colors: &Colors, flags: &Flags, val_alignment: usize) -> ColoredString {
+        if flags.size_align.0 {
+            return self.render_aligned(colors, flags, val_alignment);
+        }
+
         let val_content = self.render_value(colors, flags);
         let unit_content = self.render_unit(colors, flags);
 
-        let mut left_pad = String::with_capacity(val_alignment - val_content.len());
-        for _ in 0..left_pad.capacity() {
-            left_pad.push(' ');
+        let mut left_pad = String::new();
+        for _ in 0..val_alignment.saturating_sub(val_content.len()) {
+            left_pad.push(flags.padding.fill());
         }
 
         let mut strings: Vec<ColoredString> = vec![ColoredString::from(left_pad), val_content];
@@ -71,6 +103,37 @@ impl Size {
         ColoredString::from(res)
     }
 
+    /// Render the size with the integer part of the value right-aligned to `int_alignment`,
+    /// so the decimal points and the units of a listing line up in fixed columns.
+    fn render_aligned(&self, colors: &Colors, flags: &Flags, int_alignment: usize) -> ColoredString {
+        let value = self.value_string(flags);
+        let mut parts = value.splitn(2, '.');
+
+        let integer = parts.next().unwrap_or_default();
+        let mut content = String::new();
+        for _ in 0..int_alignment.saturating_sub(integer.chars().count()) {
+            content.push(flags.padding.fill());
+        }
+        content.push_str(integer);
+        match parts.next() {
+            Some(fraction) => {
+                content.push('.');
+                content.push_str(fraction);
+            }
+            // Pad for the missing fraction, so the units stay in their column.
+            None => content.push_str("  "),
+        }
+
+        let mut strings: Vec<ColoredString> = vec![self.paint(colors, flags, content)];
+        if flags.size != SizeFlag::Short {
+            strings.push(ColoredString::from(" "));
+        }
+        strings.push(self.render_unit(colors, flags));
+
+        let res = ANSIStrings(&strings).to_string();
+        ColoredString::from(res)
+    }
+
     fn paint(&self, colors: &Colors, flags: &Flags, content: String) -> ColoredString {
         let unit = self.get_unit(flags);
 
@@ -94,18 +157,20 @@ impl Size {
     pub fn value_string(&self, flags: &Flags) -> String {
         let unit = self.get_unit(flags);
 
+        let base = Self::unit_base(flags) as f64;
+
         match unit {
             Unit::None => "".to_string(),
             Unit::Byte => self.bytes.to_string(),
-            Unit::Kilo => self.format_size(((self.bytes as f64) / 1024.0 * 10.0).round() / 10.0),
+            Unit::Kilo => self.format_size(((self.bytes as f64) / base * 10.0).round() / 10.0),
             Unit::Mega => {
-                self.format_size(((self.bytes as f64) / (1024.0 * 1024.0) * 10.0).round() / 10.0)
+                self.format_size(((self.bytes as f64) / (base * base) * 10.0).round() / 10.0)
             }
             Unit::Giga => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
+                ((self.bytes as f64) / (base * base * base) * 10.0).round() / 10.0,
             ),
             Unit::Tera => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
+                ((self.bytes as f64) / (base * base * base * base) * 10.0).round() / 10.0,
             ),
         }
     }
@@ -113,7 +178,13 @@ impl Size {
     pub fn render_unit(&self, colors: &Colors, flags: &Flags) -> ColoredString {
         let content = self.unit_string(flags);
 
-        self.paint(colors, flags, content)
+        // The unit gets its own, magnitude independent color, so the suffixes stand apart
+        // from the numbers when scanning the size column.
+        if self.get_unit(flags) == Unit::None {
+            colors.colorize(content, &Elem::NonFile)
+        } else {
+            colors.colorize(content, &Elem::SizeUnit)
+        }
     }
 
     pub fn unit_string(&self, flags: &Flags) -> String {
@@ -123,6 +194,12 @@ impl Size {
             SizeFlag::Default => match unit {
                 Unit::None => String::from("-"),
                 Unit::Byte => String::from("B"),
+                // The binary prefixes make the 1024 base explicit; the historic default
+                // keeps the short labels.
+                Unit::Kilo if flags.size_unit == SizeUnitFlag::Binary => String::from("KiB"),
+                Unit::Mega if flags.size_unit == SizeUnitFlag::Binary => String::from("MiB"),
+                Unit::Giga if flags.size_unit == SizeUnitFlag::Binary => String::from("GiB"),
+                Unit::Tera if flags.size_unit == SizeUnitFlag::Binary => String::from("TiB"),
                 Unit::Kilo => String::from("KB"),
                 Unit::Mega => String::from("MB"),
                 Unit::Giga => String::from("GB"),
@@ -144,8 +221,8 @@ impl Size {
 #[cfg(test)]
 mod test {
     use super::Size;
-    use crate::color::{Colors, Theme};
-    use crate::flags::{Flags, SizeFlag};
+    use crate::color::{Colors, Palette, Theme};
+    use crate::flags::{Flags, SizeFlag, SizeUnitFlag};
 
     #[test]
     fn render_byte() {
@@ -172,6 +249,41 @@ mod test {
         assert_eq!(size.unit_string(&flags).as_str(), "K");
     }
 
+    #[test]
+    fn render_kilobyte_binary_units() {
+        let size = Size::new(42 * 1024); // 42 kibibytes
+        let mut flags = Flags::default();
+        flags.size_unit = SizeUnitFlag::Binary;
+
+        assert_eq!(size.value_string(&flags).as_str(), "42");
+        assert_eq!(size.unit_string(&flags).as_str(), "KiB");
+    }
+
+    #[test]
+    fn render_kilobyte_decimal_units() {
+        let size = Size::new(42 * 1000); // 42 kilobytes
+        let mut flags = Flags::default();
+        flags.size_unit = SizeUnitFlag::Decimal;
+
+        assert_eq!(size.value_string(&flags).as_str(), "42");
+        assert_eq!(size.unit_string(&flags).as_str(), "KB");
+
+        // The same value stays below the binary kilobyte threshold.
+        flags.size_unit = SizeUnitFlag::Binary;
+        assert_eq!(size.value_string(&flags).as_str(), "41");
+        assert_eq!(size.unit_string(&flags).as_str(), "KiB");
+    }
+
+    #[test]
+    fn render_megabyte_decimal_units() {
+        let size = Size::new(42 * 1000 * 1000); // 42 megabytes
+        let mut flags = Flags::default();
+        flags.size_unit = SizeUnitFlag::Decimal;
+
+        assert_eq!(size.value_string(&flags).as_str(), "42");
+        assert_eq!(size.unit_string(&flags).as_str(), "MB");
+    }
+
     #[test]
     fn render_kilobyte() {
         let size = Size::new(42 * 1024); // 42 kilobytes
@@ -311,12 +423,25 @@ mod test {
         assert_eq!(size.unit_string(&flags).as_str(), "KB");
     }
 
+    #[test]
+    fn render_aligned() {
+        let mut flags = Flags::default();
+        flags.size_align = crate::flags::SizeAlign(true);
+        let colors = Colors::new(Theme::NoColor, Palette::Default);
+
+        let fractional = Size::new(4 * 1024); // 4.0 kilobytes
+        let whole = Size::new(42 * 1024); // 42 kilobytes
+
+        assert_eq!(fractional.render(&colors, &flags, 2).to_string(), " 4.0 KB");
+        assert_eq!(whole.render(&colors, &flags, 2).to_string(), "42   KB");
+    }
+
     #[test]
     fn render_short_nospaces() {
         let size = Size::new(42 * 1024); // 42 kilobytes
         let mut flags = Flags::default();
         flags.size = SizeFlag::Short;
-        let colors = Colors::new(Theme::NoColor);
+        let colors = Colors::new(Theme::NoColor, Palette::Default);
 
         assert_eq!(size.render(&colors, &flags, 2).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, 3).to_string(), " 42K");
@@ -3,7 +3,7 @@ use ansi_term::{ANSIString, ANSIStrings};
 use std::fs::read_link;
 use std::path::Path;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct SymLink {
     target: Option<String>,
     valid: bool,
@@ -51,6 +51,64 @@ impl SymLink {
         }
     }
 
+    /// Render the full resolution chain of nested symlinks, following at most `depth` hops:
+    /// ` => b => c [final: /etc/real]`. A chain cut short by the depth cap ends in an
+    /// ellipsis; entries which are no symlink render empty like [render](SymLink::render).
+    pub fn render_chain(&self, path: &Path, colors: &Colors, depth: usize) -> ColoredString {
+        if self.target.is_none() || depth == 0 {
+            return self.render(colors);
+        }
+
+        let mut strings: Vec<ColoredString> = Vec::new();
+        let mut current = path.to_path_buf();
+        let mut hops = 0;
+
+        while hops < depth {
+            let target = match read_link(&current) {
+                Ok(target) => target,
+                Err(_) => break,
+            };
+
+            let resolved = if target.is_absolute() {
+                target.clone()
+            } else {
+                match current.parent() {
+                    Some(parent) => parent.join(&target),
+                    None => target.clone(),
+                }
+            };
+
+            let elem = if resolved.exists() {
+                &Elem::SymLink
+            } else {
+                &Elem::BrokenSymLink
+            };
+
+            strings.push(ColoredString::from(" \u{21d2} ")); // ⇒
+            strings.push(colors.colorize(target.to_string_lossy().to_string(), elem));
+
+            current = resolved;
+            hops += 1;
+        }
+
+        if read_link(&current).is_ok() {
+            // The cap cut the chain short, which an ellipsis is more honest about than a
+            // silently final looking target.
+            strings.push(ColoredString::from(" \u{21d2} \u{2026}"));
+        } else if hops > 1 {
+            // The final annotation only carries information when the chain took more than
+            // one hop, and it is context next to the targets, so it renders dimmed.
+            let final_path = current.canonicalize().unwrap_or(current);
+            strings.push(colors.colorize(
+                format!(" [final: {}]", final_path.display()),
+                &Elem::NoAccess,
+            ));
+        }
+
+        let res = ANSIStrings(&strings).to_string();
+        ColoredString::from(res)
+    }
+
     pub fn render(&self, colors: &Colors) -> ColoredString {
         if let Some(target_string) = self.symlink_string() {
             let elem = if self.valid {
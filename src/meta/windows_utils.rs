@@ -1,7 +1,7 @@
 use std::ffi::{OsStr, OsString};
 use std::io;
 use std::os::windows::ffi::{OsStrExt, OsStringExt};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf, Prefix};
 use std::ptr::null_mut;
 
 use winapi::ctypes::c_void;
@@ -13,7 +13,42 @@ use super::{Owner, Permissions};
 
 const BUF_SIZE: u32 = 256;
 
-pub fn get_file_data(path: &Path) -> Result<(Owner, Permissions), io::Error> {
+/// The classic Win32 path length limit, beyond which plain paths stop working and the
+/// extended-length (`\\?\`) syntax is needed.
+const MAX_PATH: usize = 260;
+
+/// Return the extended-length form of the given path when it exceeds the classic Win32 path
+/// limit, so metadata calls and recursion keep working in deep trees. Shorter paths and paths
+/// which already carry a verbatim prefix are returned unchanged.
+pub fn extended_length(path: &Path) -> PathBuf {
+    if path.as_os_str().len() < MAX_PATH {
+        return path.to_path_buf();
+    }
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        match std::env::current_dir() {
+            Ok(current) => current.join(path),
+            Err(_) => return path.to_path_buf(),
+        }
+    };
+
+    match absolute.components().next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            // Already extended-length; adding another prefix would break the path.
+            Prefix::Verbatim(_) | Prefix::VerbatimUNC(..) | Prefix::VerbatimDisk(_) => absolute,
+            Prefix::UNC(..) => PathBuf::from(format!(
+                r"\\?\UNC\{}",
+                absolute.to_string_lossy().trim_start_matches('\\')
+            )),
+            _ => PathBuf::from(format!(r"\\?\{}", absolute.to_string_lossy())),
+        },
+        _ => absolute,
+    }
+}
+
+pub fn get_file_data(path: &Path, attributes: u32) -> Result<(Owner, Permissions), io::Error> {
     // Overall design:
     // This function allocates some data with GetNamedSecurityInfoW,
     // manipulates it only through WinAPI calls (treating the pointers as
@@ -65,25 +100,14 @@ pub fn get_file_data(path: &Path) -> Result<(Owner, Permissions), io::Error> {
     // - owner_sid_ptr is valid
     // - group_sid_ptr is valid
     // (both OK because GetNamedSecurityInfoW returned success)
-    let (owner_name, owner_domain) = unsafe { lookup_account_sid(owner_sid_ptr) }?;
-    let (group_name, group_domain) = unsafe { lookup_account_sid(group_sid_ptr) }?;
-
-    let owner_name = os_from_buf(&owner_name);
-    let owner_domain = os_from_buf(&owner_domain);
-    let group_name = os_from_buf(&group_name);
-    let group_domain = os_from_buf(&group_domain);
+    let owner_sid = unsafe { sid_to_string(owner_sid_ptr) }.ok();
+    let group_sid = unsafe { sid_to_string(group_sid_ptr) }.ok();
 
-    // Format into domain\name format
-    let mut owner = owner_domain.to_string_lossy().into_owned();
-    owner.push('\\');
-    owner.push_str(&owner_name.to_string_lossy());
-
-    let mut group = group_domain.to_string_lossy().into_owned();
-    group.push('\\');
-    group.push_str(&group_name.to_string_lossy());
+    let owner = unsafe { account_from_sid(owner_sid_ptr, &owner_sid) };
+    let group = unsafe { account_from_sid(group_sid_ptr, &group_sid) };
 
     // This structure will be returned
-    let owner = Owner::new(owner, group);
+    let owner = Owner::with_sids(owner, group, owner_sid, group_sid);
 
     // Get the size and allocate bytes for a 1-sub-authority SID
     // 1 sub-authority because the Windows World SID is always S-1-1-0, with
@@ -156,6 +180,8 @@ pub fn get_file_data(path: &Path) -> Result<(Owner, Permissions), io::Error> {
         sticky: false,
         setuid: false,
         setgid: false,
+
+        attributes,
     };
 
     // Assumptions:
@@ -215,6 +241,59 @@ unsafe fn trustee_from_sid(sid_ptr: *mut c_void) -> TRUSTEE_W {
     trustee
 }
 
+/// Resolve a SID into a `domain\name` string.
+///
+/// When the SID can not be resolved into an account name (deleted accounts, foreign domains),
+/// this falls back to the raw SID string, so the owner block never ends up empty.
+///
+/// Assumption: sid is a valid pointer that remains valid through the entire
+/// function execution
+unsafe fn account_from_sid(sid: *mut c_void, sid_string: &Option<String>) -> String {
+    match lookup_account_sid(sid) {
+        Ok((name, domain)) => {
+            let name = os_from_buf(&name);
+            let domain = os_from_buf(&domain);
+
+            // Format into domain\name format
+            let mut account = domain.to_string_lossy().into_owned();
+            account.push('\\');
+            account.push_str(&name.to_string_lossy());
+            account
+        }
+        Err(_) => sid_string.clone().unwrap_or_else(|| String::from("?")),
+    }
+}
+
+/// Convert a SID into its string form (e.g. `S-1-5-21-...`).
+///
+/// Assumption: sid is a valid pointer that remains valid through the entire
+/// function execution
+unsafe fn sid_to_string(sid: *mut c_void) -> Result<String, io::Error> {
+    let mut string_ptr: winnt::LPWSTR = null_mut();
+
+    // Assumptions:
+    // - sid is a valid pointer to a SID data structure
+    // - string_ptr is only valid after the return value is checked and must be
+    //   freed with LocalFree
+    if winapi::shared::sddl::ConvertSidToStringSidW(sid, &mut string_ptr) == 0 {
+        return Err(io::Error::from_raw_os_error(
+            winapi::um::errhandlingapi::GetLastError() as i32,
+        ));
+    }
+
+    let mut len = 0;
+    while *string_ptr.add(len) != 0 {
+        len += 1;
+    }
+    let result = os_from_buf(std::slice::from_raw_parts(string_ptr, len + 1))
+        .to_string_lossy()
+        .into_owned();
+
+    winapi::um::winbase::LocalFree(string_ptr as *mut _);
+
+    Ok(result)
+}
+
 /// Get a username and domain name from a SID
 ///
 /// Assumption: sid is a valid pointer that remains valid through the entire
@@ -0,0 +1,307 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+/// The names of the POSIX ACL attributes on Linux, which get the `+` suffix instead of the
+/// generic `@` one.
+#[cfg(target_os = "linux")]
+const ACL_NAMES: &[&str] = &["system.posix_acl_access", "system.posix_acl_default"];
+#[cfg(not(target_os = "linux"))]
+const ACL_NAMES: &[&str] = &[];
+
+/// The names of the xattrs storing the security label on Linux, in lookup order.
+#[cfg(target_os = "linux")]
+const SECURITY_NAMES: &[&str] = &["security.selinux", "security.SMACK64"];
+#[cfg(not(target_os = "linux"))]
+const SECURITY_NAMES: &[&str] = &[];
+
+/// The extended attribute names of an entry, gathered only when a listing displays the
+/// permission block. The values are read lazily, since only the extended mode shows them.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct XAttrs {
+    names: Vec<String>,
+}
+
+impl XAttrs {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub fn from_path(path: &Path) -> Self {
+        Self {
+            names: list_names(path),
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    pub fn from_path(_: &Path) -> Self {
+        Self::default()
+    }
+
+    /// Whether one of the attributes stores a POSIX ACL.
+    fn has_acl(&self) -> bool {
+        self.names.iter().any(|name| ACL_NAMES.contains(&name.as_str()))
+    }
+
+    /// The one character suffix of the permission block: `+` when the entry carries an ACL
+    /// and `@` when it carries other extended attributes, like GNU and BSD ls.
+    pub fn render_suffix(&self, colors: &Colors) -> Option<ColoredString> {
+        let suffix = if self.has_acl() {
+            "+"
+        } else if !self.names.is_empty() {
+            "@"
+        } else {
+            return None;
+        };
+
+        Some(colors.colorize(String::from(suffix), &Elem::NoAccess))
+    }
+
+    /// The security label of the entry for the context block, like `ls -Z`: the SELinux or
+    /// SMACK label, or `?` where there is none.
+    pub fn render_context(&self, path: &Path, colors: &Colors) -> ColoredString {
+        for name in SECURITY_NAMES {
+            if !self.names.iter().any(|candidate| candidate == name) {
+                continue;
+            }
+
+            if let Some(mut value) = read_value(path, name) {
+                while value.last() == Some(&0) {
+                    value.pop();
+                }
+
+                if !value.is_empty() {
+                    let label = String::from_utf8_lossy(&value).into_owned();
+                    return colors.colorize(label, &Elem::Context);
+                }
+            }
+        }
+
+        colors.colorize(String::from("?"), &Elem::Context)
+    }
+
+    /// The attribute names paired with a printable form of their values, read from the given
+    /// path. Values which do not read as printable text are summarized by their length.
+    pub fn entries(&self, path: &Path) -> Vec<(String, String)> {
+        self.names
+            .iter()
+            .map(|name| (name.clone(), printable_value(path, name)))
+            .collect()
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn printable_value(path: &Path, name: &str) -> String {
+    let value = match read_value(path, name) {
+        Some(value) => value,
+        None => return String::from("<unreadable>"),
+    };
+
+    match String::from_utf8(value) {
+        Ok(text) if text.chars().all(|c| !c.is_control()) => text,
+        Ok(text) => format!("<{} bytes>", text.len()),
+        Err(error) => format!("<{} bytes>", error.as_bytes().len()),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn printable_value(_: &Path, _: &str) -> String {
+    String::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_value(_: &Path, _: &str) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn c_path(path: &Path) -> Option<std::ffi::CString> {
+    use std::os::unix::ffi::OsStrExt;
+
+    std::ffi::CString::new(path.as_os_str().as_bytes()).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn list_names(path: &Path) -> Vec<String> {
+    let path = match c_path(path) {
+        Some(path) => path,
+        None => return Vec::new(),
+    };
+
+    // Most entries carry no or only a few attributes, so a fixed buffer keeps the common
+    // case at a single syscall per entry. Only an overflowing list pays for the second,
+    // properly sized call; a list shrinking between the two calls just yields another
+    // error, which is treated like no attributes at all.
+    let mut buffer = vec![0u8; LIST_BUFFER_SIZE];
+    let mut size = unsafe {
+        libc::llistxattr(
+            path.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_char,
+            buffer.len(),
+        )
+    };
+
+    if size < 0 && std::io::Error::last_os_error().raw_os_error() == Some(libc::ERANGE) {
+        let needed = unsafe { libc::llistxattr(path.as_ptr(), std::ptr::null_mut(), 0) };
+        if needed <= 0 {
+            return Vec::new();
+        }
+
+        buffer = vec![0u8; needed as usize];
+        size = unsafe {
+            libc::llistxattr(
+                path.as_ptr(),
+                buffer.as_mut_ptr() as *mut libc::c_char,
+                buffer.len(),
+            )
+        };
+    }
+
+    if size <= 0 {
+        return Vec::new();
+    }
+
+    buffer.truncate(size as usize);
+    names_from_list(&buffer)
+}
+
+#[cfg(target_os = "macos")]
+fn list_names(path: &Path) -> Vec<String> {
+    let path = match c_path(path) {
+        Some(path) => path,
+        None => return Vec::new(),
+    };
+
+    // Like on Linux, a fixed buffer keeps the common case at a single syscall per entry.
+    let mut buffer = vec![0u8; LIST_BUFFER_SIZE];
+    let mut size = unsafe {
+        libc::listxattr(
+            path.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_char,
+            buffer.len(),
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+
+    if size < 0 && std::io::Error::last_os_error().raw_os_error() == Some(libc::ERANGE) {
+        let needed = unsafe {
+            libc::listxattr(path.as_ptr(), std::ptr::null_mut(), 0, libc::XATTR_NOFOLLOW)
+        };
+        if needed <= 0 {
+            return Vec::new();
+        }
+
+        buffer = vec![0u8; needed as usize];
+        size = unsafe {
+            libc::listxattr(
+                path.as_ptr(),
+                buffer.as_mut_ptr() as *mut libc::c_char,
+                buffer.len(),
+                libc::XATTR_NOFOLLOW,
+            )
+        };
+    }
+
+    if size <= 0 {
+        return Vec::new();
+    }
+
+    buffer.truncate(size as usize);
+    names_from_list(&buffer)
+}
+
+/// The size of the name list buffer tried first, large enough for the attribute lists of
+/// nearly all entries.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const LIST_BUFFER_SIZE: usize = 512;
+
+/// Split the NUL separated name list the listxattr calls return.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn names_from_list(buffer: &[u8]) -> Vec<String> {
+    buffer
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_value(path: &Path, name: &str) -> Option<Vec<u8>> {
+    let path = c_path(path)?;
+    let name = std::ffi::CString::new(name).ok()?;
+
+    let size = unsafe { libc::lgetxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+    if size < 0 {
+        return None;
+    }
+
+    let mut buffer = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::lgetxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+        )
+    };
+    if size < 0 {
+        return None;
+    }
+
+    buffer.truncate(size as usize);
+    Some(buffer)
+}
+
+#[cfg(target_os = "macos")]
+fn read_value(path: &Path, name: &str) -> Option<Vec<u8>> {
+    let path = c_path(path)?;
+    let name = std::ffi::CString::new(name).ok()?;
+
+    let size = unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            0,
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+    if size < 0 {
+        return None;
+    }
+
+    let mut buffer = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::getxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+            0,
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+    if size < 0 {
+        return None;
+    }
+
+    buffer.truncate(size as usize);
+    Some(buffer)
+}
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod tests {
+    use super::XAttrs;
+    use std::env;
+    use std::fs::File;
+
+    #[test]
+    fn test_fresh_file_has_no_suffix() {
+        let mut file_path = env::temp_dir();
+        file_path.push("xattr.tmp");
+        File::create(&file_path).unwrap();
+
+        let xattrs = XAttrs::from_path(&file_path);
+
+        assert!(xattrs.names.is_empty());
+        assert!(xattrs.entries(&file_path).is_empty());
+    }
+}
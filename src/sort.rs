@@ -1,25 +1,26 @@
+use crate::flags::sorting::{Collate, SymlinkGrouping};
 use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
-use crate::meta::Meta;
+use crate::meta::{FileType, Meta};
 use human_sort::compare;
 use std::cmp::Ordering;
 
+/// The listing size above which [sort_metas] switches to pre-computed keys and sorts the
+/// halves on separate threads. Below it, the plain comparator sort wins.
+const LARGE_LISTING: usize = 8192;
+
 pub type SortFn = fn(&Meta, &Meta) -> Ordering;
 
 pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
     let mut sorters: Vec<(SortOrder, SortFn)> = vec![];
-    match flags.sorting.dir_grouping {
-        DirGrouping::First => {
-            sorters.push((SortOrder::Default, with_dirs_first));
-        }
-        DirGrouping::Last => {
-            sorters.push((SortOrder::Reverse, with_dirs_first));
-        }
-        DirGrouping::None => {}
-    };
+    push_groupings(&mut sorters, flags);
     let other_sort = match flags.sorting.column {
+        SortColumn::Name if flags.sorting.collate == Collate::Locale => by_name_locale,
+        SortColumn::Name if flags.sorting.natural.0 => by_name_natural,
         SortColumn::Name => by_name,
         SortColumn::Size => by_size,
         SortColumn::Time => by_date,
+        SortColumn::INode => by_inode,
+        SortColumn::Type => by_type,
         SortColumn::Version => by_version,
         SortColumn::Extension => by_extension,
     };
@@ -42,10 +43,39 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
     Ordering::Equal
 }
 
+/// The grouping criteria run before the sort column and compose in order: entries are
+/// grouped by directory placement first, then by symlink placement within each group.
+fn push_groupings(sorters: &mut Vec<(SortOrder, SortFn)>, flags: &Flags) {
+    match flags.sorting.dir_grouping {
+        DirGrouping::First => {
+            sorters.push((SortOrder::Default, with_dirs_first));
+        }
+        DirGrouping::Last => {
+            sorters.push((SortOrder::Reverse, with_dirs_first));
+        }
+        DirGrouping::None => {}
+    };
+    match flags.sorting.symlink_grouping {
+        SymlinkGrouping::First => {
+            sorters.push((SortOrder::Default, with_symlinks_first));
+        }
+        SymlinkGrouping::Last => {
+            sorters.push((SortOrder::Reverse, with_symlinks_first));
+        }
+        SymlinkGrouping::None => {}
+    };
+}
+
 fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
     b.file_type.is_dirlike().cmp(&a.file_type.is_dirlike())
 }
 
+fn with_symlinks_first(a: &Meta, b: &Meta) -> Ordering {
+    let a_link = matches!(a.file_type, FileType::SymLink { .. });
+    let b_link = matches!(b.file_type, FileType::SymLink { .. });
+    b_link.cmp(&a_link)
+}
+
 fn by_size(a: &Meta, b: &Meta) -> Ordering {
     b.size.get_bytes().cmp(&a.size.get_bytes())
 }
@@ -54,10 +84,135 @@ fn by_name(a: &Meta, b: &Meta) -> Ordering {
     a.name.cmp(&b.name)
 }
 
+fn by_name_natural(a: &Meta, b: &Meta) -> Ordering {
+    natural_cmp(a.name.lowercase(), b.name.lowercase())
+}
+
+fn by_name_locale(a: &Meta, b: &Meta) -> Ordering {
+    locale_cmp(&a.name.name, &b.name.name)
+}
+
+/// Compare two names with the collation rules of the user's locale, so accented and
+/// non-Latin names sort the way the locale expects. The collation locale is picked up from
+/// the environment once, on the first comparison.
+#[cfg(unix)]
+pub fn locale_cmp(a: &str, b: &str) -> Ordering {
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+    INIT.call_once(|| unsafe {
+        libc::setlocale(libc::LC_COLLATE, b"\0".as_ptr() as *const libc::c_char);
+    });
+
+    let (a_c, b_c) = match (std::ffi::CString::new(a), std::ffi::CString::new(b)) {
+        (Ok(a_c), Ok(b_c)) => (a_c, b_c),
+        // Interior NUL bytes can not reach strcoll, so such names compare bytewise.
+        _ => return a.cmp(b),
+    };
+
+    let result = unsafe { libc::strcoll(a_c.as_ptr(), b_c.as_ptr()) };
+    result.cmp(&0)
+}
+
+/// Without strcoll the locale collation falls back to the bytewise ordering.
+#[cfg(not(unix))]
+pub fn locale_cmp(a: &str, b: &str) -> Ordering {
+    a.cmp(b)
+}
+
+/// Compare two names the way a human reads them: runs of ASCII digits are ordered by their
+/// numeric value, everything else character by character, so `file2` sorts before `file10`.
+/// Only ASCII digits are treated as numbers, which keeps the ordering locale independent.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x_run = take_digits(&mut a_chars);
+                let y_run = take_digits(&mut b_chars);
+                match compare_digit_runs(&x_run, &y_run) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+            }
+            (Some(x), Some(y)) => match x.cmp(&y) {
+                Ordering::Equal => {
+                    a_chars.next();
+                    b_chars.next();
+                }
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+/// Consume the run of ASCII digits the iterator currently points at.
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        run.push(*c);
+        chars.next();
+    }
+    run
+}
+
+/// Compare two digit runs by their numeric value without parsing them, so arbitrarily long
+/// runs can not overflow: after stripping leading zeros a longer run is larger and equally
+/// long runs compare lexically. Runs equal in value fall back to their lengths, so names
+/// differing only in zero padding still get a stable order.
+fn compare_digit_runs(a: &str, b: &str) -> Ordering {
+    let a_value = a.trim_start_matches('0');
+    let b_value = b.trim_start_matches('0');
+
+    a_value
+        .len()
+        .cmp(&b_value.len())
+        .then_with(|| a_value.cmp(b_value))
+        .then_with(|| a.len().cmp(&b.len()))
+}
+
 fn by_date(a: &Meta, b: &Meta) -> Ordering {
     b.date.cmp(&a.date).then(a.name.cmp(&b.name))
 }
 
+fn by_inode(a: &Meta, b: &Meta) -> Ordering {
+    a.inode
+        .number()
+        .cmp(&b.inode.number())
+        .then(by_name(a, b))
+}
+
+/// The category rank of the type sort: the ordering graphical file managers use, with
+/// directories first, then symlinks and executables, then regular entries.
+fn type_rank(file_type: &FileType) -> u8 {
+    match file_type {
+        FileType::Directory { .. } => 0,
+        FileType::SymLink { .. } => 1,
+        FileType::File { exec: true, .. } => 2,
+        _ => 3,
+    }
+}
+
+fn by_type(a: &Meta, b: &Meta) -> Ordering {
+    let rank = type_rank(&a.file_type);
+    let ordering = rank.cmp(&type_rank(&b.file_type));
+
+    // Only the regular entries group by extension, so directories stay alphabetical.
+    if ordering == Ordering::Equal && rank == 3 {
+        by_extension(a, b).then(by_name(a, b))
+    } else {
+        ordering.then(by_name(a, b))
+    }
+}
+
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
     compare(&a.name.name, &b.name.name)
 }
@@ -270,6 +425,80 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_t), Ordering::Less);
     }
 
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+        assert_eq!(natural_cmp("file2", "file2"), Ordering::Equal);
+        assert_eq!(natural_cmp("file02", "file2"), Ordering::Greater);
+        assert_eq!(natural_cmp("a2b10", "a2b9"), Ordering::Greater);
+        assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+        assert_eq!(
+            natural_cmp("99999999999999999999998", "99999999999999999999999"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_group_symlinks() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a).expect("failed to create file");
+        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join("zzz");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&path_a, &path_z).expect("failed to create symlink");
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(&path_a, &path_z).expect("failed to create symlink");
+        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        flags.sorting.symlink_grouping = SymlinkGrouping::First;
+
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
+
+        flags.sorting.symlink_grouping = SymlinkGrouping::Last;
+
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
+    }
+
+    #[test]
+    fn test_locale_cmp_ascii() {
+        // The collation order of non-ASCII names depends on the environment's locale, so
+        // only the ordering of plain ASCII, which every locale agrees on, is asserted.
+        assert_eq!(locale_cmp("apple", "banana"), Ordering::Less);
+        assert_eq!(locale_cmp("banana", "apple"), Ordering::Greater);
+        assert_eq!(locale_cmp("apple", "apple"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_name_natural() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let path_a = tmp_dir.path().join("file2");
+        File::create(&path_a).expect("failed to create file");
+        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+
+        let path_b = tmp_dir.path().join("file10");
+        File::create(&path_b).expect("failed to create file");
+        let meta_b = Meta::from_path(&path_b, false).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+
+        // The plain name sort puts file10 before file2.
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_b), Ordering::Greater);
+
+        // The natural name sort compares the embedded numbers by value.
+        flags.sorting.natural = crate::flags::sorting::NaturalSort(true);
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_b), Ordering::Less);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_version() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -296,3 +525,128 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
 }
+
+/// Sort a listing of [Meta] according to the [Flags].
+///
+/// Small listings use the assembled comparators directly. For very large directories the
+/// comparators are too slow, since comparisons like the case insensitive name ordering
+/// allocate on every call; those listings are sorted with a key computed once per entry,
+/// with the two halves sorted on separate threads and merged.
+pub fn sort_metas(metas: &mut Vec<Meta>, flags: &Flags, sorters: &[(SortOrder, SortFn)]) {
+    if metas.len() < LARGE_LISTING {
+        metas.sort_unstable_by(|a, b| by_meta(sorters, a, b));
+        return;
+    }
+
+    let mut decorated: Vec<(SortKey, Meta)> = metas
+        .drain(..)
+        .map(|meta| (SortKey::new(&meta, flags), meta))
+        .collect();
+
+    let second_half = decorated.split_off(decorated.len() / 2);
+    let flags_clone = flags.clone();
+    let handle = std::thread::spawn(move || {
+        let mut half = second_half;
+        half.sort_unstable_by(|(a, _), (b, _)| a.compare(b, &flags_clone));
+        half
+    });
+    decorated.sort_unstable_by(|(a, _), (b, _)| a.compare(b, flags));
+    let second_half = handle.join().expect("sort thread panicked");
+
+    // Merge the two sorted halves back into the result.
+    let mut left = decorated.into_iter().peekable();
+    let mut right = second_half.into_iter().peekable();
+    while let (Some((left_key, _)), Some((right_key, _))) = (left.peek(), right.peek()) {
+        if left_key.compare(right_key, flags) != Ordering::Greater {
+            metas.push(left.next().unwrap().1);
+        } else {
+            metas.push(right.next().unwrap().1);
+        }
+    }
+    metas.extend(left.map(|(_, meta)| meta));
+    metas.extend(right.map(|(_, meta)| meta));
+}
+
+/// The per-entry keys for the large-listing sort, computed once instead of inside the
+/// comparator.
+struct SortKey {
+    dirlike: bool,
+    symlink: bool,
+    type_rank: u8,
+    inode: Option<u64>,
+    lowercase_name: String,
+    name: String,
+    extension: Option<String>,
+    size: u64,
+    date: crate::meta::Date,
+}
+
+impl SortKey {
+    fn new(meta: &Meta, _flags: &Flags) -> Self {
+        Self {
+            dirlike: meta.file_type.is_dirlike(),
+            symlink: matches!(meta.file_type, FileType::SymLink { .. }),
+            type_rank: type_rank(&meta.file_type),
+            inode: meta.inode.number(),
+            lowercase_name: meta.name.lowercase().to_string(),
+            name: meta.name.name.clone(),
+            extension: meta.name.extension().map(str::to_string),
+            size: meta.size.get_bytes(),
+            date: meta.date.clone(),
+        }
+    }
+
+    /// Compare two keys with the same ordering as the comparators assembled from the
+    /// [Flags] in [assemble_sorters].
+    fn compare(&self, other: &Self, flags: &Flags) -> Ordering {
+        let grouping = match flags.sorting.dir_grouping {
+            DirGrouping::First => other.dirlike.cmp(&self.dirlike),
+            DirGrouping::Last => self.dirlike.cmp(&other.dirlike),
+            DirGrouping::None => Ordering::Equal,
+        };
+        let grouping = grouping.then(match flags.sorting.symlink_grouping {
+            SymlinkGrouping::First => other.symlink.cmp(&self.symlink),
+            SymlinkGrouping::Last => self.symlink.cmp(&other.symlink),
+            SymlinkGrouping::None => Ordering::Equal,
+        });
+
+        let column = match flags.sorting.column {
+            SortColumn::Name if flags.sorting.collate == Collate::Locale => {
+                locale_cmp(&self.name, &other.name)
+            }
+            SortColumn::Name if flags.sorting.natural.0 => {
+                natural_cmp(&self.lowercase_name, &other.lowercase_name)
+            }
+            SortColumn::Name => self.lowercase_name.cmp(&other.lowercase_name),
+            SortColumn::Size => other.size.cmp(&self.size),
+            SortColumn::Time => other
+                .date
+                .cmp(&self.date)
+                .then(self.lowercase_name.cmp(&other.lowercase_name)),
+            SortColumn::Type => {
+                let ordering = self.type_rank.cmp(&other.type_rank);
+                let extension = if ordering == Ordering::Equal && self.type_rank == 3 {
+                    self.extension.cmp(&other.extension)
+                } else {
+                    Ordering::Equal
+                };
+
+                ordering
+                    .then(extension)
+                    .then(self.lowercase_name.cmp(&other.lowercase_name))
+            }
+            SortColumn::INode => self
+                .inode
+                .cmp(&other.inode)
+                .then(self.lowercase_name.cmp(&other.lowercase_name)),
+            SortColumn::Version => compare(&self.name, &other.name),
+            SortColumn::Extension => self.extension.cmp(&other.extension),
+        };
+        let column = match flags.sorting.order {
+            SortOrder::Default => column,
+            SortOrder::Reverse => column.reverse(),
+        };
+
+        grouping.then(column)
+    }
+}
@@ -305,6 +305,22 @@ fn test_show_folder_of_symlink_for_long_multi() {
         .stdout(predicate::str::contains("link/:"));
 }
 
+#[test]
+fn test_tree_from_stdin() {
+    let dir = tempdir();
+    dir.child("one").child("two").touch().unwrap();
+
+    assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .arg("--tree")
+        .arg("--stdin")
+        .arg("--ignore-config")
+        .write_stdin(format!("{}\n", dir.path().join("one/two").display()))
+        .assert()
+        .stdout(predicate::str::contains("one"))
+        .stdout(predicate::str::contains("└── two"));
+}
+
 #[test]
 fn test_version_sort() {
     let dir = tempdir();
@@ -327,6 +343,26 @@ fn test_version_sort() {
         );
 }
 
+#[test]
+fn test_size_sort_with_total_size() {
+    use std::fs::File;
+    use std::io::Write;
+    let dir = tempdir();
+    dir.child("small/inner").touch().unwrap();
+    let large = dir.path().join("large/inner");
+    std::fs::create_dir(dir.path().join("large")).unwrap();
+    let mut large_file = File::create(large).unwrap();
+    // Larger than the inode size of a directory, so the recursive size decides the order.
+    writeln!(large_file, "{}", "x".repeat(1024 * 1024)).unwrap();
+    cmd()
+        .arg("-S")
+        .arg("--total-size")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("large\nsmall\n$").unwrap());
+}
+
 #[test]
 fn test_version_sort_overwrite_by_timesort() {
     let dir = tempdir();
@@ -397,6 +433,19 @@ fn test_bad_utf_8_name() {
         .stdout(predicate::str::is_match("bad-name\u{fffd}\u{fffd}.ext\n$").unwrap());
 }
 
+#[test]
+fn test_json_schema_version() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--json")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with("{\"schema_version\":1,\"entries\":["));
+}
+
 fn cmd() -> Command {
     Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
 }